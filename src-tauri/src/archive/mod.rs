@@ -0,0 +1,166 @@
+//! 压缩包格式抽象
+//!
+//! 预览和安装都通过 [`ModArchive`] 读取压缩包，格式由文件头的魔数识别，
+//! 与扩展名无关。
+
+mod rar;
+mod sevenz;
+mod tar;
+mod zip;
+
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+
+/// 压缩包中的条目
+#[derive(Debug, Clone)]
+pub struct ArchiveEntry {
+    /// 压缩包内记录的原始路径
+    pub name: String,
+    /// 校验后的相对路径；绝对路径或包含 `..` 等越出解压目录的条目为 `None`
+    pub path: Option<PathBuf>,
+    pub is_dir: bool,
+    /// 解压后的大小
+    pub size: u64,
+}
+
+impl ArchiveEntry {
+    pub fn new(name: String, is_dir: bool, size: u64) -> Self {
+        let path = enclosed_path(&name);
+        Self {
+            name,
+            path,
+            is_dir,
+            size,
+        }
+    }
+
+    /// 是否为表示压缩包根目录的空条目（如 7z 中名称为空的目录）
+    pub fn is_root(&self) -> bool {
+        self.name
+            .split(['/', '\\'])
+            .all(|part| part.is_empty() || part == ".")
+    }
+}
+
+/// 压缩包读取接口
+pub trait ModArchive {
+    /// 列出压缩包中的所有条目
+    fn entries(&mut self) -> Result<Vec<ArchiveEntry>, String>;
+
+    /// 解压条目到 `dest`
+    ///
+    /// 每个条目都会调用 `filter`，返回值为相对 `dest` 的输出路径，返回 `None`
+    /// 时跳过该条目，返回错误时中止解压。
+    fn extract(
+        &mut self,
+        dest: &Path,
+        filter: &mut dyn FnMut(&ArchiveEntry) -> Result<Option<PathBuf>, String>,
+    ) -> Result<(), String>;
+}
+
+/// 支持的压缩包格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Zip,
+    SevenZ,
+    Rar,
+    Tar,
+    TarGz,
+    TarXz,
+}
+
+/// 根据文件头的魔数识别压缩包格式
+pub fn detect_format(path: &Path) -> Result<ArchiveFormat, String> {
+    let mut header = [0u8; 262];
+    let mut file = fs::File::open(path).map_err(|e| format!("无法打开压缩包: {}", e))?;
+    let len = read_up_to(&mut file, &mut header).map_err(|e| format!("无法读取压缩包: {}", e))?;
+    let header = &header[..len];
+
+    if header.starts_with(b"PK\x03\x04")
+        || header.starts_with(b"PK\x05\x06")
+        || header.starts_with(b"PK\x07\x08")
+    {
+        Ok(ArchiveFormat::Zip)
+    } else if header.starts_with(b"7z\xBC\xAF\x27\x1C") {
+        Ok(ArchiveFormat::SevenZ)
+    } else if header.starts_with(b"Rar!\x1A\x07") {
+        Ok(ArchiveFormat::Rar)
+    } else if header.starts_with(b"\x1F\x8B") {
+        Ok(ArchiveFormat::TarGz)
+    } else if header.starts_with(b"\xFD7zXZ\x00") {
+        Ok(ArchiveFormat::TarXz)
+    } else if header.get(257..262) == Some(b"ustar") {
+        Ok(ArchiveFormat::Tar)
+    } else {
+        Err("不支持的压缩包格式（支持 zip、7z、rar、tar.gz、tar.xz）".to_string())
+    }
+}
+
+/// 打开压缩包
+pub fn open_archive(path: &Path) -> Result<Box<dyn ModArchive>, String> {
+    Ok(match detect_format(path)? {
+        ArchiveFormat::Zip => Box::new(zip::ZipModArchive::open(path)?),
+        ArchiveFormat::SevenZ => Box::new(sevenz::SevenZModArchive::open(path)?),
+        ArchiveFormat::Rar => Box::new(rar::RarModArchive::open(path)),
+        ArchiveFormat::Tar => Box::new(tar::TarModArchive::open(path, tar::Compression::None)),
+        ArchiveFormat::TarGz => Box::new(tar::TarModArchive::open(path, tar::Compression::Gzip)),
+        ArchiveFormat::TarXz => Box::new(tar::TarModArchive::open(path, tar::Compression::Xz)),
+    })
+}
+
+/// 将压缩包内的路径转换为安全的相对路径
+///
+/// 同时接受 `/` 和 `\` 作为分隔符；绝对路径、盘符以及 `..` 均视为不安全。
+fn enclosed_path(name: &str) -> Option<PathBuf> {
+    if name.starts_with('/') || name.starts_with('\\') {
+        return None;
+    }
+
+    let mut path = PathBuf::new();
+    for part in name.split(['/', '\\']) {
+        if part.is_empty() || part == "." {
+            continue;
+        }
+        if part == ".." || part.contains(':') {
+            return None;
+        }
+        match Path::new(part).components().next() {
+            Some(Component::Normal(_)) => path.push(part),
+            _ => return None,
+        }
+    }
+
+    if path.as_os_str().is_empty() {
+        None
+    } else {
+        Some(path)
+    }
+}
+
+/// 将条目内容写入文件，自动创建父目录
+fn write_entry(output_path: &Path, reader: &mut dyn Read) -> Result<(), String> {
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("无法创建父目录: {}", e))?;
+    }
+
+    let mut output_file = fs::File::create(output_path)
+        .map_err(|e| format!("无法创建文件 {}: {}", output_path.display(), e))?;
+
+    io::copy(reader, &mut output_file)
+        .map_err(|e| format!("无法写入文件 {}: {}", output_path.display(), e))?;
+
+    Ok(())
+}
+
+/// 尽可能填满缓冲区，返回实际读取的字节数
+fn read_up_to(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
@@ -0,0 +1,76 @@
+use super::{ArchiveEntry, ModArchive};
+use std::fs;
+use std::path::{Path, PathBuf};
+use unrar::Archive;
+
+/// rar 压缩包（包括分卷）
+pub struct RarModArchive {
+    path: PathBuf,
+}
+
+impl RarModArchive {
+    pub fn open(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+        }
+    }
+}
+
+fn to_entry(header: &unrar::FileHeader) -> ArchiveEntry {
+    ArchiveEntry::new(
+        header.filename.to_string_lossy().to_string(),
+        header.is_directory(),
+        header.unpacked_size,
+    )
+}
+
+impl ModArchive for RarModArchive {
+    fn entries(&mut self) -> Result<Vec<ArchiveEntry>, String> {
+        let archive = Archive::new(&self.path)
+            .open_for_listing()
+            .map_err(|e| format!("无法读取压缩包: {}", e))?;
+
+        archive
+            .map(|header| {
+                let header = header.map_err(|e| format!("无法读取文件: {}", e))?;
+                Ok(to_entry(&header))
+            })
+            .collect()
+    }
+
+    fn extract(
+        &mut self,
+        dest: &Path,
+        filter: &mut dyn FnMut(&ArchiveEntry) -> Result<Option<PathBuf>, String>,
+    ) -> Result<(), String> {
+        let mut archive = Archive::new(&self.path)
+            .open_for_processing()
+            .map_err(|e| format!("无法读取压缩包: {}", e))?;
+
+        while let Some(header) = archive
+            .read_header()
+            .map_err(|e| format!("无法读取文件: {}", e))?
+        {
+            let entry = to_entry(header.entry());
+            let output_path = filter(&entry)?.map(|relative| dest.join(relative));
+
+            archive = match output_path {
+                Some(output_path) if entry.is_dir => {
+                    fs::create_dir_all(&output_path)
+                        .map_err(|e| format!("无法创建目录 {}: {}", entry.name, e))?;
+                    header.skip()
+                }
+                Some(output_path) => {
+                    if let Some(parent) = output_path.parent() {
+                        fs::create_dir_all(parent).map_err(|e| format!("无法创建父目录: {}", e))?;
+                    }
+                    header.extract_to(&output_path)
+                }
+                None => header.skip(),
+            }
+            .map_err(|e| format!("解压文件 {} 失败: {}", entry.name, e))?;
+        }
+
+        Ok(())
+    }
+}
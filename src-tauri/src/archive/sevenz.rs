@@ -0,0 +1,78 @@
+use super::{write_entry, ArchiveEntry, ModArchive};
+use sevenz_rust::{Password, SevenZReader};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// 7z 压缩包
+pub struct SevenZModArchive {
+    reader: SevenZReader<fs::File>,
+}
+
+impl SevenZModArchive {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let reader = SevenZReader::open(path, Password::empty())
+            .map_err(|e| format!("无法读取压缩包: {}", e))?;
+        Ok(Self { reader })
+    }
+}
+
+impl ModArchive for SevenZModArchive {
+    fn entries(&mut self) -> Result<Vec<ArchiveEntry>, String> {
+        Ok(self
+            .reader
+            .archive()
+            .files
+            .iter()
+            .map(|file| {
+                ArchiveEntry::new(file.name().to_string(), file.is_directory(), file.size())
+            })
+            .collect())
+    }
+
+    fn extract(
+        &mut self,
+        dest: &Path,
+        filter: &mut dyn FnMut(&ArchiveEntry) -> Result<Option<PathBuf>, String>,
+    ) -> Result<(), String> {
+        // 回调只能返回 sevenz_rust::Error，先记录下自己的错误再中止遍历
+        let mut failure = None;
+
+        self.reader
+            .for_each_entries(|file, reader| {
+                let entry =
+                    ArchiveEntry::new(file.name().to_string(), file.is_directory(), file.size());
+                let result = filter(&entry).and_then(|relative| {
+                    let Some(relative) = relative else {
+                        // 固实压缩下各文件共用同一个解码流，跳过的文件也必须读完，
+                        // 否则后面的文件会读到它的数据
+                        return io::copy(reader, &mut io::sink())
+                            .map(|_| ())
+                            .map_err(|e| format!("解压文件 {} 失败: {}", entry.name, e));
+                    };
+                    let output_path = dest.join(relative);
+
+                    if entry.is_dir {
+                        fs::create_dir_all(&output_path)
+                            .map_err(|e| format!("无法创建目录 {}: {}", entry.name, e))
+                    } else {
+                        write_entry(&output_path, reader)
+                    }
+                });
+
+                match result {
+                    Ok(()) => Ok(true),
+                    Err(e) => {
+                        failure = Some(e);
+                        Ok(false)
+                    }
+                }
+            })
+            .map_err(|e| format!("解压失败: {}", e))?;
+
+        match failure {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use super::{write_entry, ArchiveEntry, ModArchive};
+use flate2::read::GzDecoder;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use xz2::read::XzDecoder;
+
+/// tar 包的压缩方式
+#[derive(Debug, Clone, Copy)]
+pub enum Compression {
+    None,
+    Gzip,
+    Xz,
+}
+
+/// tar / tar.gz / tar.xz 压缩包
+///
+/// tar 只能顺序读取，每次遍历都重新打开文件。
+pub struct TarModArchive {
+    path: PathBuf,
+    compression: Compression,
+}
+
+impl TarModArchive {
+    pub fn open(path: &Path, compression: Compression) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            compression,
+        }
+    }
+
+    fn reader(&self) -> Result<tar::Archive<Box<dyn Read>>, String> {
+        let file = fs::File::open(&self.path).map_err(|e| format!("无法打开压缩包: {}", e))?;
+        let reader: Box<dyn Read> = match self.compression {
+            Compression::None => Box::new(file),
+            Compression::Gzip => Box::new(GzDecoder::new(file)),
+            Compression::Xz => Box::new(XzDecoder::new(file)),
+        };
+        Ok(tar::Archive::new(reader))
+    }
+}
+
+/// 读取 tar 条目信息；链接等特殊条目不解压，视为不安全条目
+fn to_entry<R: Read>(entry: &tar::Entry<R>) -> ArchiveEntry {
+    let header = entry.header();
+    let name = String::from_utf8_lossy(&entry.path_bytes()).to_string();
+    let entry_type = header.entry_type();
+
+    let mut archive_entry =
+        ArchiveEntry::new(name, entry_type.is_dir(), header.size().unwrap_or(0));
+    if !entry_type.is_file() && !entry_type.is_dir() {
+        archive_entry.path = None;
+    }
+    archive_entry
+}
+
+impl ModArchive for TarModArchive {
+    fn entries(&mut self) -> Result<Vec<ArchiveEntry>, String> {
+        let mut archive = self.reader()?;
+        let entries = archive
+            .entries()
+            .map_err(|e| format!("无法读取压缩包: {}", e))?;
+
+        entries
+            .map(|entry| {
+                let entry = entry.map_err(|e| format!("无法读取文件: {}", e))?;
+                Ok(to_entry(&entry))
+            })
+            .collect()
+    }
+
+    fn extract(
+        &mut self,
+        dest: &Path,
+        filter: &mut dyn FnMut(&ArchiveEntry) -> Result<Option<PathBuf>, String>,
+    ) -> Result<(), String> {
+        let mut archive = self.reader()?;
+        let entries = archive
+            .entries()
+            .map_err(|e| format!("无法读取压缩包: {}", e))?;
+
+        for entry in entries {
+            let mut entry = entry.map_err(|e| format!("无法读取文件: {}", e))?;
+            let archive_entry = to_entry(&entry);
+            let Some(relative) = filter(&archive_entry)? else {
+                continue;
+            };
+            let output_path = dest.join(relative);
+
+            if archive_entry.is_dir {
+                fs::create_dir_all(&output_path)
+                    .map_err(|e| format!("无法创建目录 {}: {}", archive_entry.name, e))?;
+            } else {
+                write_entry(&output_path, &mut entry)?;
+            }
+        }
+
+        Ok(())
+    }
+}
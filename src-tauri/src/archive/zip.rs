@@ -0,0 +1,63 @@
+use super::{write_entry, ArchiveEntry, ModArchive};
+use ::zip::ZipArchive;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// zip 压缩包
+pub struct ZipModArchive {
+    archive: ZipArchive<fs::File>,
+}
+
+impl ZipModArchive {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let file = fs::File::open(path).map_err(|e| format!("无法打开压缩包: {}", e))?;
+        let archive = ZipArchive::new(file).map_err(|e| format!("无法读取压缩包: {}", e))?;
+        Ok(Self { archive })
+    }
+}
+
+impl ModArchive for ZipModArchive {
+    fn entries(&mut self) -> Result<Vec<ArchiveEntry>, String> {
+        (0..self.archive.len())
+            .map(|i| {
+                let file = self
+                    .archive
+                    .by_index(i)
+                    .map_err(|e| format!("无法读取文件: {}", e))?;
+                Ok(ArchiveEntry::new(
+                    file.name().to_string(),
+                    file.is_dir(),
+                    file.size(),
+                ))
+            })
+            .collect()
+    }
+
+    fn extract(
+        &mut self,
+        dest: &Path,
+        filter: &mut dyn FnMut(&ArchiveEntry) -> Result<Option<PathBuf>, String>,
+    ) -> Result<(), String> {
+        for i in 0..self.archive.len() {
+            let mut file = self
+                .archive
+                .by_index(i)
+                .map_err(|e| format!("无法读取文件 {}: {}", i, e))?;
+
+            let entry = ArchiveEntry::new(file.name().to_string(), file.is_dir(), file.size());
+            let Some(relative) = filter(&entry)? else {
+                continue;
+            };
+            let output_path = dest.join(relative);
+
+            if entry.is_dir {
+                fs::create_dir_all(&output_path)
+                    .map_err(|e| format!("无法创建目录 {}: {}", entry.name, e))?;
+            } else {
+                write_entry(&output_path, &mut file)?;
+            }
+        }
+
+        Ok(())
+    }
+}
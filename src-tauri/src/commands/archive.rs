@@ -1,8 +1,11 @@
+use crate::archive::{open_archive, ArchiveEntry};
+use crate::commands::file_ops::merge_case_variants;
+use crate::commands::jobs::Job;
 use crate::models::{ArchiveFileNode, ArchivePreview, OperationResult};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
-use zip::ZipArchive;
 
 /// 检查目录中是否存在 nativepc 文件夹（大小写不敏感）
 pub fn find_nativepc_folder(path: &Path) -> Option<PathBuf> {
@@ -23,8 +26,14 @@ pub fn find_nativepc_folder(path: &Path) -> Option<PathBuf> {
     None
 }
 
-/// 将 nativepc 文件夹统一重命名为小写
-pub fn normalize_nativepc_folder(path: &Path) -> Result<(), String> {
+/// 规范化 MOD 目录中的路径大小写
+///
+/// 递归合并仅大小写不同的同名目录（如 `nativePC/Assets` 与 `nativepc/assets`），
+/// 然后将 nativepc 文件夹统一重命名为小写。返回合并时因重名被丢弃的条目。
+pub fn normalize_nativepc_folder(path: &Path) -> Result<Vec<String>, String> {
+    let dropped =
+        merge_case_variants(path).map_err(|e| format!("无法合并大小写不同的目录: {}", e))?;
+
     if let Some(nativepc_path) = find_nativepc_folder(path) {
         let file_name = nativepc_path
             .file_name()
@@ -33,7 +42,7 @@ pub fn normalize_nativepc_folder(path: &Path) -> Result<(), String> {
 
         // 如果已经是小写，则无需操作
         if file_name == "nativepc" {
-            return Ok(());
+            return Ok(dropped);
         }
 
         // 重命名为小写
@@ -42,75 +51,374 @@ pub fn normalize_nativepc_folder(path: &Path) -> Result<(), String> {
             .map_err(|e| format!("无法重命名文件夹: {}", e))?;
     }
 
-    Ok(())
+    Ok(dropped)
 }
 
-/// 预览 zip 压缩包内容
-#[tauri::command]
-pub fn preview_zip_archive(archive_path: String) -> Result<ArchivePreview, String> {
-    let file = fs::File::open(&archive_path)
-        .map_err(|e| format!("无法打开压缩包: {}", e))?;
-
-    let mut archive = ZipArchive::new(file)
-        .map_err(|e| format!("无法读取压缩包: {}", e))?;
-
-    let mut has_native_pc = false;
-    let mut native_pc_path = String::new();
-    let mut file_tree: Vec<ArchiveFileNode> = Vec::new();
-
-    // 读取压缩包中的所有文件
-    for i in 0..archive.len() {
-        let file = archive.by_index(i)
-            .map_err(|e| format!("无法读取文件: {}", e))?;
-
-        let file_path = file.name().to_string();
-        let is_directory = file.is_dir();
-
-        // 检查是否包含 nativepc 文件夹
-        if !has_native_pc {
-            let path_lower = file_path.to_lowercase();
-            if path_lower.contains("nativepc/") || path_lower.contains("nativepc\\") {
-                has_native_pc = true;
-                // 提取 nativepc 的路径部分
-                if let Some(idx) = path_lower.find("nativepc") {
-                    native_pc_path = file_path[..idx + 8].to_string();
-                }
+/// 将条目路径拆分为各级名称
+fn path_parts(path: &Path) -> Vec<String> {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect()
+}
+
+/// 查找压缩包中所有直接包含 nativepc 文件夹的目录
+///
+/// 返回 (安装根目录, nativepc 文件夹路径)，均以 `/` 分隔，空字符串表示压缩包
+/// 根目录；按深度从浅到深排列。
+fn find_nativepc_roots(entries: &[ArchiveEntry]) -> Vec<(String, String)> {
+    let mut roots = BTreeSet::new();
+
+    for entry in entries {
+        let Some(path) = &entry.path else {
+            continue;
+        };
+        let parts = path_parts(path);
+
+        // nativepc 必须是目录：要么后面还有路径，要么条目本身是目录
+        let found = parts.iter().enumerate().position(|(i, part)| {
+            part.to_lowercase() == "nativepc" && (i + 1 < parts.len() || entry.is_dir)
+        });
+        if let Some(index) = found {
+            roots.insert((index, parts[..index].join("/"), parts[..=index].join("/")));
+        }
+    }
+
+    roots
+        .into_iter()
+        .map(|(_, root, native_pc_path)| (root, native_pc_path))
+        .collect()
+}
+
+/// 剥离外层包装目录
+///
+/// 只要当前层级没有文件且只有一个子目录（如 `SomeMod v1.2/`），就继续向下一层。
+fn strip_wrapper_folders(entries: &[ArchiveEntry]) -> String {
+    let paths: Vec<(Vec<String>, bool)> = entries
+        .iter()
+        .filter_map(|entry| entry.path.as_ref().map(|p| (path_parts(p), entry.is_dir)))
+        .collect();
+
+    let mut root: Vec<String> = Vec::new();
+    loop {
+        let mut children = BTreeSet::new();
+        let mut has_files = false;
+
+        for (parts, is_dir) in &paths {
+            if parts.len() <= root.len() || parts[..root.len()] != root[..] {
+                continue;
+            }
+            if parts.len() == root.len() + 1 && !is_dir {
+                has_files = true;
             }
+            children.insert(parts[root.len()].clone());
         }
 
-        // 构建文件树节点
-        file_tree.push(ArchiveFileNode {
-            name: Path::new(&file_path)
-                .file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or(&file_path)
-                .to_string(),
-            path: file_path,
-            is_directory,
-            children: None,
-        });
+        if has_files || children.len() != 1 {
+            break;
+        }
+        root.extend(children);
+    }
+
+    root.join("/")
+}
+
+/// 列出压缩包中所有可安装的根目录（包含 nativepc 及同级 DLL 等文件的目录）
+///
+/// 压缩包可能包含多个变体（如 `Option A/nativePC`、`Option B/nativePC`），
+/// 按深度从浅到深排列；没有 nativepc 时只返回剥离外层包装目录后的目录。
+pub fn detect_install_roots(entries: &[ArchiveEntry]) -> Vec<String> {
+    let roots: Vec<String> = find_nativepc_roots(entries)
+        .into_iter()
+        .map(|(root, _)| root)
+        .collect();
+
+    if roots.is_empty() {
+        vec![strip_wrapper_folders(entries)]
+    } else {
+        roots
+    }
+}
+
+/// 计算条目相对安装根目录的路径（大小写不敏感），不在安装根目录下时返回 `None`
+fn relative_to_root(path: &Path, root: &str) -> Option<PathBuf> {
+    let parts = path_parts(path);
+    let root_parts: Vec<&str> = root.split('/').filter(|p| !p.is_empty()).collect();
+
+    if parts.len() <= root_parts.len() {
+        return None;
+    }
+
+    let under_root = parts
+        .iter()
+        .zip(&root_parts)
+        .all(|(part, root_part)| part.to_lowercase() == root_part.to_lowercase());
+    if !under_root {
+        return None;
+    }
+
+    Some(parts[root_parts.len()..].iter().collect())
+}
+
+/// 规范化根目录路径，用于大小写不敏感地比较
+fn root_key(root: &str) -> String {
+    root.split('/')
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+        .to_lowercase()
+}
+
+/// 计算每个条目（按压缩包内原始路径）的输出路径
+///
+/// 条目归属到包含它的最深的候选根目录（包含 nativepc 的目录和 `install_roots`），
+/// 因此嵌套在所选根目录中、但未被选择的变体（如 `Mod/Optional/nativePC`）不会
+/// 被一并解压。多个根目录提供同一文件（忽略大小写）时，`install_roots` 中排在
+/// 前面的根目录优先。
+fn plan_extraction(
+    entries: &[ArchiveEntry],
+    install_roots: &[String],
+) -> Result<HashMap<String, PathBuf>, String> {
+    // 同一根目录可能以不同大小写重复出现，只保留第一次出现的写法
+    let mut install_roots: Vec<&String> = install_roots.iter().collect();
+    let mut seen = BTreeSet::new();
+    install_roots.retain(|root| seen.insert(root_key(root)));
+
+    let selected: Vec<String> = install_roots.iter().map(|root| root_key(root)).collect();
+    let mut candidates: Vec<String> = find_nativepc_roots(entries)
+        .iter()
+        .map(|(root, _)| root_key(root))
+        .collect();
+    for root in &selected {
+        if !candidates.contains(root) {
+            candidates.push(root.clone());
+        }
+    }
+
+    let assigned: Vec<(&ArchiveEntry, usize, PathBuf)> = entries
+        .iter()
+        .filter_map(|entry| {
+            let path = entry.path.as_ref()?;
+            let (owner, relative) = candidates
+                .iter()
+                .filter_map(|root| relative_to_root(path, root).map(|relative| (root, relative)))
+                .min_by_key(|(_, relative)| relative.components().count())?;
+            let index = selected.iter().position(|root| root == owner)?;
+
+            // 嵌套的其他根目录本身及其上级目录只作为容器，不需要在所属根目录中创建
+            let key = root_key(&path_parts(path).join("/"));
+            let contains_nested_root = candidates
+                .iter()
+                .any(|root| root != owner && (*root == key || is_under(root, &key)));
+            if entry.is_dir && contains_nested_root {
+                return None;
+            }
+
+            Some((entry, index, relative))
+        })
+        .collect();
+
+    for (index, root) in install_roots.iter().enumerate() {
+        if !assigned.iter().any(|(_, i, _)| *i == index) {
+            return Err(format!("压缩包中不存在安装根目录: {}", root));
+        }
+    }
+
+    // 同一文件由多个根目录提供时，记录优先级最高的根目录
+    let mut winners: HashMap<String, usize> = HashMap::new();
+    for (entry, index, relative) in &assigned {
+        if entry.is_dir {
+            continue;
+        }
+        let key = relative.to_string_lossy().to_lowercase();
+        let winner = winners.entry(key).or_insert(*index);
+        *winner = (*winner).min(*index);
+    }
+
+    Ok(assigned
+        .into_iter()
+        .filter(|(entry, index, relative)| {
+            entry.is_dir || winners.get(&relative.to_string_lossy().to_lowercase()) == Some(index)
+        })
+        .map(|(entry, _, relative)| (entry.name.clone(), relative))
+        .collect())
+}
+
+/// 判断 `path` 是否位于 `root` 之下（大小写不敏感，`root` 为空表示根目录）
+fn is_under(path: &str, root: &str) -> bool {
+    let (path, root) = (path.to_lowercase(), root.to_lowercase());
+    root.is_empty() || path.strip_prefix(&root).is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// 构建文件树时使用的中间节点
+#[derive(Default)]
+struct TreeBuilder {
+    is_dir: bool,
+    size: u64,
+    children: BTreeMap<String, TreeBuilder>,
+}
+
+impl TreeBuilder {
+    /// 插入条目，并补全压缩包中省略的上级目录节点
+    fn insert(&mut self, parts: &[String], is_dir: bool, size: u64) {
+        let Some((first, rest)) = parts.split_first() else {
+            return;
+        };
+
+        let node = self.children.entry(first.clone()).or_default();
+        if rest.is_empty() {
+            node.is_dir |= is_dir;
+            node.size = size;
+        } else {
+            node.is_dir = true;
+            node.insert(rest, is_dir, size);
+        }
+    }
+
+    /// 转换为文件树节点，并汇总目录的大小和文件数量
+    ///
+    /// `install_root` 和 `native_pc_path` 用于标记 nativepc 根目录以及会被
+    /// 复制到游戏根目录的文件；`nested_roots` 是嵌套在安装根目录中的其他变体，
+    /// 它们不会随安装根目录一起解压。目录中有文件会落到游戏根目录时，目录也会被标记。
+    fn into_nodes(
+        self,
+        parent: &str,
+        install_root: &str,
+        native_pc_path: &str,
+        nested_roots: &[String],
+    ) -> Vec<ArchiveFileNode> {
+        let mut nodes: Vec<ArchiveFileNode> = self
+            .children
+            .into_iter()
+            .map(|(name, builder)| {
+                let path = if parent.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}/{}", parent, name)
+                };
+                let is_native_pc_root = !native_pc_path.is_empty()
+                    && path.to_lowercase() == native_pc_path.to_lowercase();
+
+                let is_directory = builder.is_dir;
+                let (size, file_count, lands_in_game_root, children) = if is_directory {
+                    let children =
+                        builder.into_nodes(&path, install_root, native_pc_path, nested_roots);
+                    let size = children.iter().map(|c| c.size).sum();
+                    let file_count = children.iter().map(|c| c.file_count).sum();
+                    let lands = children.iter().any(|c| c.lands_in_game_root);
+                    (size, file_count, lands, Some(children))
+                } else {
+                    let lands = is_under(&path, install_root)
+                        && (native_pc_path.is_empty() || !is_under(&path, native_pc_path))
+                        && !nested_roots.iter().any(|root| is_under(&path, root));
+                    (builder.size, 1, lands, None)
+                };
+
+                ArchiveFileNode {
+                    name,
+                    path,
+                    is_directory,
+                    size,
+                    file_count,
+                    is_native_pc_root,
+                    lands_in_game_root,
+                    children,
+                }
+            })
+            .collect();
+
+        // 目录排在文件前面
+        nodes.sort_by_key(|node| !node.is_directory);
+        nodes
     }
+}
+
+/// 预览压缩包内容（支持 zip、7z、rar、tar.gz、tar.xz）
+#[tauri::command]
+pub fn preview_archive(archive_path: String) -> Result<ArchivePreview, String> {
+    let mut archive = open_archive(Path::new(&archive_path))?;
+    let entries = archive.entries()?;
+
+    let nativepc_roots = find_nativepc_roots(&entries);
+    let has_native_pc = !nativepc_roots.is_empty();
+    let native_pc_path = nativepc_roots
+        .first()
+        .map(|(_, path)| path.clone())
+        .unwrap_or_default();
+    let install_roots = detect_install_roots(&entries);
+    let install_root = install_roots.first().cloned().unwrap_or_default();
+
+    // 构建文件树，不展示越出解压目录的条目（解压时也会跳过它们）
+    let mut tree = TreeBuilder::default();
+    for entry in &entries {
+        if let Some(path) = &entry.path {
+            tree.insert(&path_parts(path), entry.is_dir, entry.size);
+        }
+    }
+
+    let default_native_pc_path = nativepc_roots
+        .iter()
+        .find(|(root, _)| *root == install_root)
+        .map(|(_, path)| path.as_str())
+        .unwrap_or_default();
+    let nested_roots: Vec<String> = install_roots
+        .iter()
+        .filter(|root| root_key(root) != root_key(&install_root) && is_under(root, &install_root))
+        .cloned()
+        .collect();
+    let files = tree.into_nodes("", &install_root, default_native_pc_path, &nested_roots);
 
     Ok(ArchivePreview {
         has_native_pc,
         native_pc_path,
-        files: file_tree,
+        install_root,
+        install_roots,
+        file_count: files.iter().map(|f| f.file_count).sum(),
+        total_size: files.iter().map(|f| f.size).sum(),
+        files,
     })
 }
 
-/// 解压 zip 文件到指定目录
+/// 解压压缩包到指定目录（支持 zip、7z、rar、tar.gz、tar.xz）
+///
+/// 只解压所选安装根目录下的内容并剥离外层包装目录。选择多个根目录时按顺序
+/// 合并，排在前面的优先；未指定时使用自动检测到的第一个根目录（见
+/// [`detect_install_roots`]）。绝对路径或包含 `..` 越出目标目录的条目，以及与
+/// 其他文件仅大小写不同的重名文件会被跳过，并记录在结果的 `skipped` 中。
 #[tauri::command]
-pub fn extract_zip_archive(
+pub fn extract_archive(
     archive_path: String,
     destination: String,
+    install_roots: Option<Vec<String>>,
+) -> Result<OperationResult, String> {
+    extract_archive_with_job(
+        Path::new(&archive_path),
+        Path::new(&destination),
+        install_roots,
+        &Job::detached(),
+    )
+}
+
+/// 解压压缩包，逐个文件报告进度，取消时中止并返回错误
+pub fn extract_archive_with_job(
+    archive_path: &Path,
+    dest_path: &Path,
+    install_roots: Option<Vec<String>>,
+    job: &Job,
 ) -> Result<OperationResult, String> {
-    let file = fs::File::open(&archive_path)
-        .map_err(|e| format!("无法打开压缩包: {}", e))?;
+    let mut archive = open_archive(archive_path)?;
+    let entries = archive.entries()?;
 
-    let mut archive = ZipArchive::new(file)
-        .map_err(|e| format!("无法读取压缩包: {}", e))?;
+    let install_roots = match install_roots {
+        Some(roots) if !roots.is_empty() => roots,
+        _ => detect_install_roots(&entries).into_iter().take(1).collect(),
+    };
+    let outputs = plan_extraction(&entries, &install_roots)?;
 
-    let dest_path = Path::new(&destination);
+    let (file_count, total_size) = entries
+        .iter()
+        .filter(|entry| !entry.is_dir && outputs.contains_key(&entry.name))
+        .fold((0, 0), |(count, size), entry| (count + 1, size + entry.size));
+    job.begin(file_count, total_size);
 
     // 确保目标目录存在
     if !dest_path.exists() {
@@ -118,39 +426,37 @@ pub fn extract_zip_archive(
             .map_err(|e| format!("无法创建目标目录: {}", e))?;
     }
 
-    // 解压所有文件
-    for i in 0..archive.len() {
-        let mut file = archive.by_index(i)
-            .map_err(|e| format!("无法读取文件 {}: {}", i, e))?;
-
-        let file_path = file.name().to_string(); // 将借用的结果转换为 owned String
-        let is_dir = file.is_dir();
-        let output_path = dest_path.join(&file_path);
-
-        if is_dir {
-            // 创建目录
-            fs::create_dir_all(&output_path)
-                .map_err(|e| format!("无法创建目录 {}: {}", file_path, e))?;
-        } else {
-            // 确保父目录存在
-            if let Some(parent) = output_path.parent() {
-                fs::create_dir_all(parent)
-                    .map_err(|e| format!("无法创建父目录: {}", e))?;
+    // 解压所有文件，防止 zip-slip：只接受位于目标目录内的相对路径
+    let mut skipped = Vec::new();
+    archive.extract(dest_path, &mut |entry| {
+        job.check_cancelled()?;
+        Ok(match &entry.path {
+            Some(_) => {
+                let output = outputs.get(&entry.name).cloned();
+                if output.is_some() && !entry.is_dir {
+                    job.advance(&entry.name, entry.size);
+                }
+                output
             }
+            None => {
+                if !entry.is_root() {
+                    skipped.push(entry.name.clone());
+                }
+                None
+            }
+        })
+    })?;
 
-            // 解压文件
-            let mut output_file = fs::File::create(&output_path)
-                .map_err(|e| format!("无法创建文件 {}: {}", file_path, e))?;
-
-            io::copy(&mut file, &mut output_file)
-                .map_err(|e| format!("无法写入文件 {}: {}", file_path, e))?;
-        }
-    }
+    // 规范化 nativepc 文件夹名称，仅大小写不同的重名文件只保留一个
+    skipped.extend(normalize_nativepc_folder(dest_path)?);
 
-    // 规范化 nativepc 文件夹名称
-    normalize_nativepc_folder(dest_path)?;
+    let message = if skipped.is_empty() {
+        "解压成功".to_string()
+    } else {
+        format!("解压成功，已跳过 {} 个不安全或重名的条目", skipped.len())
+    };
 
-    Ok(OperationResult::success("解压成功"))
+    Ok(OperationResult::success(message).with_skipped(skipped))
 }
 
 /// 计算目录大小
@@ -1,16 +1,118 @@
-use crate::models::{AppConfig, ModInfo, OperationResult};
+use crate::commands::file_ops::{
+    copy_file, hash_file, list_files_recursive, with_suffix, write_file_atomic,
+};
+use crate::commands::jobs::{run_job, try_lock_data, Job};
+use crate::commands::mod_manager::sync_deployment;
+use crate::commands::naming::validate_existing_mod_name;
+use crate::models::{
+    AppConfig, DataLocation, DeploymentManifest, ModInfo, OperationResult,
+    CONFIG_SCHEMA_VERSION,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::{AppHandle, Manager};
 
+/// 读取 JSON 文件，无法读取或解析时尝试 `.bak` 备份
+///
+/// 从备份成功读取时会用备份内容恢复原文件。`what` 用于错误信息，如 "配置文件"。
+pub fn read_json_file<T: DeserializeOwned>(path: &Path, what: &str) -> Result<T, String> {
+    let error = match fs::read_to_string(path) {
+        Ok(content) => match serde_json::from_str(&content) {
+            Ok(value) => return Ok(value),
+            Err(e) => format!("无法解析{}: {}", what, e),
+        },
+        Err(e) => format!("无法读取{}: {}", what, e),
+    };
+
+    let backup_path = with_suffix(path, ".bak");
+    let Ok(content) = fs::read_to_string(&backup_path) else {
+        return Err(error);
+    };
+    let Ok(value) = serde_json::from_str(&content) else {
+        return Err(error);
+    };
+
+    eprintln!("{}，已从备份恢复: {}", error, backup_path.display());
+    if let Err(e) = write_file_atomic(path, content.as_bytes()) {
+        eprintln!("恢复{}失败: {}", what, e);
+    }
+
+    Ok(value)
+}
+
+/// 原子地写入 JSON 文件，并将原文件保留为 `.bak` 备份
+pub fn write_json_file<T: Serialize>(path: &Path, value: &T, what: &str) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("无法序列化{}: {}", what, e))?;
+
+    // 只备份能够正常解析的旧文件，避免用损坏的文件覆盖可用的备份
+    if fs::read_to_string(path)
+        .is_ok_and(|old| serde_json::from_str::<serde_json::Value>(&old).is_ok())
+    {
+        fs::copy(path, with_suffix(path, ".bak"))
+            .map_err(|e| format!("无法备份{}: {}", what, e))?;
+    }
+
+    write_file_atomic(path, content.as_bytes())
+        .map_err(|e| format!("无法写入{}: {}", what, e))
+}
+
+/// 默认的数据目录（相对于应用数据目录）
+const DEFAULT_DATA_DIRECTORY: &str = "./data";
+
+/// 获取应用数据目录
+fn get_app_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map_err(|e| format!("无法获取应用数据目录: {}", e))
+}
+
+/// 获取数据目录指针文件路径（固定位于应用数据目录下）
+fn get_data_location_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(get_app_data_dir(app)?.join("data-location.json"))
+}
+
+/// 将 `data_directory` 设置解析为绝对路径，相对路径相对于应用数据目录
+fn resolve_data_directory(app: &AppHandle, data_directory: &str) -> Result<PathBuf, String> {
+    let path = Path::new(data_directory);
+    if path.is_absolute() {
+        Ok(path.to_path_buf())
+    } else {
+        Ok(get_app_data_dir(app)?.join(path))
+    }
+}
+
+/// 读取数据目录指针中记录的位置，没有指针文件时为默认位置
+fn current_data_location(app: &AppHandle) -> Result<String, String> {
+    let location_path = get_data_location_path(app)?;
+    if !location_path.exists() {
+        return Ok(DEFAULT_DATA_DIRECTORY.to_string());
+    }
+
+    let location: DataLocation = read_json_file(&location_path, "数据目录指针")?;
+    Ok(location.data_directory)
+}
+
 /// 获取数据目录路径
+///
+/// 由 data-location.json 指定，没有该文件时使用应用数据目录下的 `data`。
 pub fn get_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("无法获取应用数据目录: {}", e))?;
+    let location_path = get_data_location_path(app)?;
+
+    if location_path.exists() {
+        let location: DataLocation = read_json_file(&location_path, "数据目录指针")?;
+        let data_dir = resolve_data_directory(app, &location.data_directory)?;
+
+        // 自定义位置（如外置硬盘）不可用时不自动创建，以免生成空的配置
+        if !data_dir.is_dir() {
+            return Err(format!("数据目录不存在: {}", data_dir.display()));
+        }
+        return Ok(data_dir);
+    }
 
-    let data_dir = app_data_dir.join("data");
+    let data_dir = resolve_data_directory(app, DEFAULT_DATA_DIRECTORY)?;
 
     // 确保数据目录存在
     if !data_dir.exists() {
@@ -21,50 +123,294 @@ pub fn get_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
     Ok(data_dir)
 }
 
+/// 获取原始游戏文件的备份目录
+pub fn get_backup_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let data_dir = get_data_dir(app)?;
+    Ok(data_dir.join(".backup"))
+}
+
+/// 获取安装 MOD 时使用的暂存目录
+///
+/// 与 MOD 目录位于同一数据目录下，保证安装完成时可以直接重命名。
+pub fn get_staging_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let data_dir = get_data_dir(app)?;
+    Ok(data_dir.join(".staging"))
+}
+
 /// 获取配置文件路径
 pub fn get_config_path(app: &AppHandle) -> Result<PathBuf, String> {
     let data_dir = get_data_dir(app)?;
     Ok(data_dir.join("config.json"))
 }
 
+/// 获取部署清单路径（与 config.json 同目录）
+pub fn get_manifest_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let data_dir = get_data_dir(app)?;
+    Ok(data_dir.join("deployment.json"))
+}
+
+/// 加载部署清单，不存在时返回 `None`
+pub fn load_manifest(app: &AppHandle) -> Result<Option<DeploymentManifest>, String> {
+    let manifest_path = get_manifest_path(app)?;
+
+    if !manifest_path.exists() {
+        return Ok(None);
+    }
+
+    read_json_file(&manifest_path, "部署清单").map(Some)
+}
+
+/// 保存部署清单
+pub fn save_manifest(app: &AppHandle, manifest: &DeploymentManifest) -> Result<(), String> {
+    let manifest_path = get_manifest_path(app)?;
+
+    write_json_file(&manifest_path, manifest, "部署清单")
+}
+
+/// 配置迁移：原地修改 JSON，将配置升级到下一个结构版本
+type Migration = fn(&mut Value) -> Result<(), String>;
+
+/// 按顺序排列的迁移，`MIGRATIONS[i]` 将版本 `i + 1` 升级到 `i + 2`
+///
+/// 只在结构发生不兼容的变化时才需要新增迁移，新增字段用 serde 默认值即可。
+const MIGRATIONS: &[Migration] = &[migrate_v1_to_v2];
+
+/// v1 -> v2：将 `order` 重新编号为 1..n
+///
+/// v1 删除 MOD 后不会重新编号，之后安装的 MOD 可能与已有 MOD 的 `order` 重复。
+fn migrate_v1_to_v2(config: &mut Value) -> Result<(), String> {
+    let Some(mods) = config.get_mut("mods").and_then(Value::as_array_mut) else {
+        return Ok(());
+    };
+
+    mods.sort_by_key(|m| m.get("order").and_then(Value::as_u64).unwrap_or(u64::MAX));
+    for (index, mod_item) in mods.iter_mut().enumerate() {
+        let item = mod_item
+            .as_object_mut()
+            .ok_or_else(|| "mods 中包含无效的条目".to_string())?;
+        item.insert("order".to_string(), Value::from(index + 1));
+    }
+
+    Ok(())
+}
+
+/// 将配置升级到 `CONFIG_SCHEMA_VERSION`
+///
+/// 返回迁移前的版本，已是最新版本时返回 `None`。
+fn migrate_config(config: &mut Value) -> Result<Option<u32>, String> {
+    let version = config
+        .get("schemaVersion")
+        .and_then(Value::as_u64)
+        .map_or(1, |v| v.max(1) as u32);
+
+    if version > CONFIG_SCHEMA_VERSION {
+        return Err(format!(
+            "配置文件版本 {} 高于当前支持的版本 {}，请升级程序",
+            version, CONFIG_SCHEMA_VERSION
+        ));
+    }
+    if version == CONFIG_SCHEMA_VERSION {
+        return Ok(None);
+    }
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version as usize - 1) {
+        let to = index as u32 + 2;
+        migration(config).map_err(|e| format!("升级配置文件到版本 {} 失败: {}", to, e))?;
+        config["schemaVersion"] = Value::from(to);
+    }
+
+    Ok(Some(version))
+}
+
 /// 加载配置文件
+///
+/// 旧版本的配置会被原地升级，升级前的文件保留为 `config.json.v<版本>.bak`。
 #[tauri::command]
 pub fn load_config(app: AppHandle) -> Result<AppConfig, String> {
     let config_path = get_config_path(&app)?;
 
     if !config_path.exists() {
         // 如果配置文件不存在，创建默认配置
-        let default_config = AppConfig::default();
-        save_config(app.clone(), default_config.clone())?;
+        let default_config = AppConfig {
+            data_directory: current_data_location(&app)?,
+            ..AppConfig::default()
+        };
+        write_config(&app, &default_config)?;
         return Ok(default_config);
     }
 
-    let content = fs::read_to_string(&config_path)
-        .map_err(|e| format!("无法读取配置文件: {}", e))?;
+    let mut raw: Value = read_json_file(&config_path, "配置文件")?;
+    let migrated_from = migrate_config(&mut raw)?;
 
-    let config: AppConfig = serde_json::from_str(&content)
+    let mut config: AppConfig = serde_json::from_value(raw)
         .map_err(|e| format!("无法解析配置文件: {}", e))?;
+    config.data_directory = current_data_location(&app)?;
+
+    if let Some(version) = migrated_from {
+        fs::copy(&config_path, with_suffix(&config_path, &format!(".v{}.bak", version)))
+            .map_err(|e| format!("无法备份配置文件: {}", e))?;
+        write_config(&app, &config)?;
+    }
 
     Ok(config)
 }
 
 /// 保存配置文件
+///
+/// `data_directory` 是只读的，修改数据目录位置需使用 `relocate_data_directory`。
 #[tauri::command]
 pub fn save_config(app: AppHandle, config: AppConfig) -> Result<OperationResult, String> {
-    let config_path = get_config_path(&app)?;
+    let _data = try_lock_data(&app)?;
+    if config.data_directory != current_data_location(&app)? {
+        return Err("数据目录不能直接修改，请使用移动数据目录功能".to_string());
+    }
+    write_config(&app, &config)?;
 
-    let content = serde_json::to_string_pretty(&config)
-        .map_err(|e| format!("无法序列化配置: {}", e))?;
+    Ok(OperationResult::success("配置已保存"))
+}
 
-    fs::write(&config_path, content)
-        .map_err(|e| format!("无法写入配置文件: {}", e))?;
+/// 写入配置文件，调用方负责持有数据锁
+pub fn write_config(app: &AppHandle, config: &AppConfig) -> Result<(), String> {
+    let config_path = get_config_path(app)?;
 
-    Ok(OperationResult::success("配置已保存"))
+    write_json_file(&config_path, config, "配置文件")
+}
+
+/// 将数据目录移动到新位置
+///
+/// 先复制所有文件并逐个校验大小和哈希，全部成功后才更新 config.json 和
+/// 数据目录指针，最后删除旧目录；中途失败或被取消时删除已复制的文件，
+/// 旧目录保持不变。进度通过 `file-progress` 事件发送。
+#[tauri::command]
+pub async fn relocate_data_directory(
+    app: AppHandle,
+    new_directory: String,
+    job_id: Option<String>,
+) -> Result<OperationResult, String> {
+    run_job(app, job_id, move |app, job| {
+        relocate_data_directory_with_job(app, &new_directory, job)
+    })
+    .await
+}
+
+fn relocate_data_directory_with_job(
+    app: &AppHandle,
+    new_directory: &str,
+    job: &Job,
+) -> Result<OperationResult, String> {
+    // 1. 验证新位置
+    let old_dir = get_data_dir(app)?;
+    let new_dir = resolve_data_directory(app, new_directory)?;
+
+    if new_dir.starts_with(&old_dir) || old_dir.starts_with(&new_dir) {
+        return Err("新数据目录不能与当前数据目录相互包含".to_string());
+    }
+    if new_dir.exists() {
+        let is_empty = fs::read_dir(&new_dir)
+            .map_err(|e| format!("无法读取新数据目录: {}", e))?
+            .next()
+            .is_none();
+        if !is_empty {
+            return Err(format!("新数据目录不为空: {}", new_dir.display()));
+        }
+    }
+
+    // 2. 复制并校验所有文件
+    fs::create_dir_all(&new_dir).map_err(|e| format!("无法创建新数据目录: {}", e))?;
+    if let Err(e) = copy_data_directory(&old_dir, &new_dir, job) {
+        let _ = fs::remove_dir_all(&new_dir);
+        return Err(e);
+    }
+
+    // 3. 更新新位置的 config.json，再切换指针
+    let config_path = new_dir.join("config.json");
+    let switched = if config_path.exists() {
+        read_json_file::<AppConfig>(&config_path, "配置文件").and_then(|mut config| {
+            config.data_directory = new_directory.to_string();
+            write_json_file(&config_path, &config, "配置文件")
+        })
+    } else {
+        Ok(())
+    }
+    .and_then(|_| {
+        let location = DataLocation {
+            data_directory: new_directory.to_string(),
+        };
+        write_json_file(&get_data_location_path(app)?, &location, "数据目录指针")
+    });
+
+    if let Err(e) = switched {
+        let _ = fs::remove_dir_all(&new_dir);
+        return Err(e);
+    }
+
+    // 4. 删除旧目录（新目录已生效，失败时只提示）
+    let mut message = match fs::remove_dir_all(&old_dir) {
+        Ok(()) => format!("数据目录已移动到 {}", new_dir.display()),
+        Err(e) => format!(
+            "数据目录已移动到 {}，但删除旧目录 {} 失败: {}",
+            new_dir.display(),
+            old_dir.display(),
+            e
+        ),
+    };
+
+    // 5. 以符号链接部署的文件仍指向旧目录，重新部署失效的文件
+    if let Ok(config) = load_config(app.clone()) {
+        if let Err(e) = sync_deployment(app, &new_dir, &config, &config, false, &Job::detached()) {
+            message = format!("{}，但重新部署失效的文件失败: {}", message, e);
+        }
+    }
+
+    Ok(OperationResult::success(message))
+}
+
+/// 复制数据目录中的所有文件，并校验大小和哈希
+fn copy_data_directory(old_dir: &Path, new_dir: &Path, job: &Job) -> Result<(), String> {
+    let files = list_files_recursive(old_dir).map_err(|e| format!("读取数据目录失败: {}", e))?;
+    let total_bytes = files
+        .iter()
+        .map(|f| fs::metadata(f).map_or(0, |m| m.len()))
+        .sum();
+
+    job.begin(files.len(), total_bytes);
+    for source in &files {
+        job.check_cancelled()?;
+
+        let relative = source.strip_prefix(old_dir).unwrap_or(source);
+        let target = new_dir.join(relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("创建目录 {} 失败: {}", parent.display(), e))?;
+        }
+        let size = copy_file(source, &target)
+            .map_err(|e| format!("复制文件 {} 失败: {}", relative.display(), e))?;
+        job.advance(&relative.to_string_lossy(), size);
+    }
+
+    // 校验阶段单独报告进度
+    job.begin(files.len(), total_bytes);
+    for source in &files {
+        job.check_cancelled()?;
+
+        let relative = source.strip_prefix(old_dir).unwrap_or(source);
+        let target = new_dir.join(relative);
+        let size = fs::metadata(source).map_or(0, |m| m.len());
+        let verified = fs::metadata(&target).is_ok_and(|m| m.len() == size)
+            && hash_file(source).ok() == hash_file(&target).ok();
+        if !verified {
+            return Err(format!("校验文件 {} 失败，复制结果与原文件不一致", relative.display()));
+        }
+        job.advance(&relative.to_string_lossy(), size);
+    }
+
+    Ok(())
 }
 
 /// 加载单个 MOD 的信息
 #[tauri::command]
 pub fn load_mod_info(app: AppHandle, mod_name: String) -> Result<ModInfo, String> {
+    validate_existing_mod_name(&mod_name)?;
     let data_dir = get_data_dir(&app)?;
     let mod_dir = data_dir.join(&mod_name);
     let mod_info_path = mod_dir.join("mod-info.json");
@@ -73,20 +419,23 @@ pub fn load_mod_info(app: AppHandle, mod_name: String) -> Result<ModInfo, String
         return Err(format!("MOD 信息文件不存在: {}", mod_name));
     }
 
-    let content = fs::read_to_string(&mod_info_path)
-        .map_err(|e| format!("无法读取 MOD 信息: {}", e))?;
-
-    let mod_info: ModInfo = serde_json::from_str(&content)
-        .map_err(|e| format!("无法解析 MOD 信息: {}", e))?;
-
-    Ok(mod_info)
+    read_json_file(&mod_info_path, " MOD 信息")
 }
 
 /// 保存单个 MOD 的信息
 #[tauri::command]
 pub fn save_mod_info(app: AppHandle, mod_name: String, mod_info: ModInfo) -> Result<OperationResult, String> {
-    let data_dir = get_data_dir(&app)?;
-    let mod_dir = data_dir.join(&mod_name);
+    let _data = try_lock_data(&app)?;
+    write_mod_info(&app, &mod_name, &mod_info)?;
+
+    Ok(OperationResult::success("MOD 信息已保存"))
+}
+
+/// 写入单个 MOD 的信息，调用方负责持有数据锁
+pub fn write_mod_info(app: &AppHandle, mod_name: &str, mod_info: &ModInfo) -> Result<(), String> {
+    validate_existing_mod_name(mod_name)?;
+    let data_dir = get_data_dir(app)?;
+    let mod_dir = data_dir.join(mod_name);
 
     // 确保 MOD 目录存在
     if !mod_dir.exists() {
@@ -95,13 +444,7 @@ pub fn save_mod_info(app: AppHandle, mod_name: String, mod_info: ModInfo) -> Res
     }
 
     let mod_info_path = mod_dir.join("mod-info.json");
-    let content = serde_json::to_string_pretty(&mod_info)
-        .map_err(|e| format!("无法序列化 MOD 信息: {}", e))?;
-
-    fs::write(&mod_info_path, content)
-        .map_err(|e| format!("无法写入 MOD 信息: {}", e))?;
-
-    Ok(OperationResult::success("MOD 信息已保存"))
+    write_json_file(&mod_info_path, mod_info, " MOD 信息")
 }
 
 /// 获取所有 MOD 的信息
@@ -0,0 +1,68 @@
+use crate::commands::{
+    config::{get_data_dir, load_config},
+    file_ops::case_insensitive_key,
+    mod_manager::collect_deploy_files,
+};
+use crate::models::{ModConfigItem, ModConflict};
+use std::collections::BTreeMap;
+use tauri::AppHandle;
+
+/// 检测 MOD 之间的文件冲突
+///
+/// 默认只统计已启用的 MOD，`include_disabled` 为 true 时同时统计未启用的 MOD。
+/// 每一对存在重叠文件的 MOD 返回一条记录，排序靠前的 MOD 为胜出方。
+#[tauri::command]
+pub fn detect_conflicts(
+    app: AppHandle,
+    include_disabled: Option<bool>,
+) -> Result<Vec<ModConflict>, String> {
+    let config = load_config(app.clone())?;
+    let data_dir = get_data_dir(&app)?;
+    let include_disabled = include_disabled.unwrap_or(false);
+
+    let mut mods: Vec<&ModConfigItem> = config
+        .mods
+        .iter()
+        .filter(|m| include_disabled || m.enabled)
+        .collect();
+    mods.sort_by_key(|m| m.order);
+
+    // 1. 建立 文件路径（忽略大小写）-> 提供该文件的 MOD（按优先级排列）索引，
+    //    同时记录优先级最高的 MOD 中的写法用于展示
+    let mut index: BTreeMap<String, (String, Vec<&ModConfigItem>)> = BTreeMap::new();
+    for mod_item in &mods {
+        let mod_dir = data_dir.join(&mod_item.name);
+        if !mod_dir.exists() {
+            eprintln!("MOD 目录不存在，跳过冲突检测: {}", mod_item.name);
+            continue;
+        }
+
+        for (target, _) in collect_deploy_files(&mod_dir)? {
+            index
+                .entry(case_insensitive_key(&target))
+                .or_insert_with(|| (target, Vec::new()))
+                .1
+                .push(mod_item);
+        }
+    }
+
+    // 2. 按 MOD 对汇总重叠文件
+    let mut pairs: BTreeMap<(usize, &str, usize, &str), ModConflict> = BTreeMap::new();
+    for (target, providers) in index.values().filter(|(_, p)| p.len() > 1) {
+        for (i, winner) in providers.iter().enumerate() {
+            for loser in &providers[i + 1..] {
+                pairs
+                    .entry((winner.order, &winner.name, loser.order, &loser.name))
+                    .or_insert_with(|| ModConflict {
+                        winner: winner.name.clone(),
+                        loser: loser.name.clone(),
+                        files: Vec::new(),
+                    })
+                    .files
+                    .push(target.clone());
+            }
+        }
+    }
+
+    Ok(pairs.into_values().collect())
+}
@@ -30,7 +30,7 @@ pub fn select_archive_file(app: AppHandle) -> Result<String, String> {
     app.dialog()
         .file()
         .set_title("选择 MOD 压缩包")
-        .add_filter("压缩包", &["zip", "rar", "7z", "tar.gz"])
+        .add_filter("压缩包", &["zip", "rar", "7z", "gz", "tgz", "xz", "txz", "tar"])
         .pick_file(move |result| {
             let _ = tx.send(result);
         });
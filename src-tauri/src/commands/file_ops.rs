@@ -1,158 +1,333 @@
-use crate::models::OperationResult;
+use crate::models::{DeployMethod, OperationResult};
+use std::collections::HashMap;
+use std::ffi::OsStr;
 use std::fs;
-use std::path::Path;
-use std::io;
-
-/// 递归复制目录
-pub fn copy_dir_recursive(src: &Path, dst: &Path) -> io::Result<()> {
-    // 创建目标目录
-    if !dst.exists() {
-        fs::create_dir_all(dst)?;
-    }
-
-    // 遍历源目录
-    for entry in fs::read_dir(src)? {
-        let entry = entry?;
-        let src_path = entry.path();
-        let file_name = entry.file_name();
-        let dst_path = dst.join(&file_name);
-
-        if src_path.is_dir() {
-            // 递归复制子目录
-            copy_dir_recursive(&src_path, &dst_path)?;
+use std::path::{Path, PathBuf};
+use std::io::{self, Write};
+
+/// 生成大小写不敏感的路径键
+///
+/// 游戏在 Windows 和 Proton 下都不区分路径大小写，`nativePC/Assets/a.tex`
+/// 与 `nativepc/assets/a.tex` 指向同一个文件。
+pub fn case_insensitive_key(relative: &str) -> String {
+    relative.to_lowercase()
+}
+
+/// 在目录中查找名称相同（忽略大小写）的条目
+pub fn find_entry_case_insensitive(dir: &Path, name: &str) -> Option<PathBuf> {
+    let name = name.to_lowercase();
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .find(|entry| entry.file_name().to_string_lossy().to_lowercase() == name)
+        .map(|entry| entry.path())
+}
+
+/// 在 `base` 下按大小写不敏感的方式解析相对路径
+///
+/// 每一级都优先沿用磁盘上已存在的写法，不存在的部分保留 `relative` 中的写法。
+pub fn resolve_case_insensitive(base: &Path, relative: &Path) -> PathBuf {
+    let mut resolved = base.to_path_buf();
+
+    for component in relative.components() {
+        let name = component.as_os_str();
+        let exact = resolved.join(name);
+        resolved = if exact.exists() {
+            exact
         } else {
-            // 复制文件
-            fs::copy(&src_path, &dst_path)?;
-        }
+            find_entry_case_insensitive(&resolved, &name.to_string_lossy()).unwrap_or(exact)
+        };
     }
 
-    Ok(())
+    resolved
 }
 
-/// 递归删除目录中的特定文件
-pub fn remove_mod_files(game_dir: &Path, mod_files: &[String]) -> io::Result<()> {
-    for file_path in mod_files {
-        let full_path = game_dir.join(file_path);
+/// 删除合并时被丢弃的条目，并记录其相对 `root` 的路径
+fn drop_entry(path: &Path, root: &Path, dropped: &mut Vec<String>) -> io::Result<()> {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    dropped.push(relative.to_string_lossy().replace('\\', "/"));
+    if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
 
-        if full_path.exists() {
-            if full_path.is_dir() {
-                fs::remove_dir_all(&full_path)?;
-            } else {
-                fs::remove_file(&full_path)?;
+/// 按名称排序列出目录内容，保证合并结果与 `read_dir` 的顺序无关
+fn sorted_entries(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    entries.sort();
+    Ok(entries)
+}
+
+/// 将 `src` 目录的内容合并到 `dst` 中（大小写不敏感），随后删除 `src`
+///
+/// 同名目录递归合并；目录与文件同名时保留目录，同名文件保留 `dst` 中已有的
+/// 版本，被丢弃的条目记录到 `dropped`。
+fn merge_dir_into(
+    src: &Path,
+    dst: &Path,
+    root: &Path,
+    dropped: &mut Vec<String>,
+) -> io::Result<()> {
+    for src_path in sorted_entries(src)? {
+        let name = src_path.file_name().unwrap_or_default().to_os_string();
+
+        match find_entry_case_insensitive(dst, &name.to_string_lossy()) {
+            Some(existing) if existing.is_dir() && src_path.is_dir() => {
+                merge_dir_into(&src_path, &existing, root, dropped)?;
             }
+            Some(existing) if src_path.is_dir() => {
+                drop_entry(&existing, root, dropped)?;
+                fs::rename(&src_path, dst.join(&name))?;
+            }
+            Some(_) => drop_entry(&src_path, root, dropped)?,
+            None => fs::rename(&src_path, dst.join(&name))?,
         }
     }
 
-    Ok(())
+    fs::remove_dir(src)
 }
 
-/// 完全删除目录
-#[tauri::command]
-pub fn delete_directory(path: String) -> Result<OperationResult, String> {
-    let dir_path = Path::new(&path);
+/// 递归合并目录中仅大小写不同的同名条目，返回被丢弃的文件（相对 `dir` 的路径）
+///
+/// 同名目录合并到按名称排序后的第一个写法中（如 `Assets` 与 `assets` 合并为
+/// `Assets`）。目录与文件同名时保留目录；同名文件保留排序后先出现的一个。
+pub fn merge_case_variants(dir: &Path) -> io::Result<Vec<String>> {
+    let mut dropped = Vec::new();
+    merge_case_variants_in(dir, dir, &mut dropped)?;
+    Ok(dropped)
+}
 
-    if !dir_path.exists() {
-        return Err("目录不存在".to_string());
+fn merge_case_variants_in(dir: &Path, root: &Path, dropped: &mut Vec<String>) -> io::Result<()> {
+    let mut seen: HashMap<String, PathBuf> = HashMap::new();
+    for path in sorted_entries(dir)? {
+        let key = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        match seen.get(&key) {
+            Some(existing) if existing.is_dir() && path.is_dir() => {
+                merge_dir_into(&path, existing, root, dropped)?;
+            }
+            Some(existing) if path.is_dir() => {
+                drop_entry(existing, root, dropped)?;
+                seen.insert(key, path);
+            }
+            Some(_) => drop_entry(&path, root, dropped)?,
+            None => {
+                seen.insert(key, path);
+            }
+        }
     }
 
-    fs::remove_dir_all(dir_path)
-        .map_err(|e| format!("删除目录失败: {}", e))?;
+    for path in seen.values().filter(|p| p.is_dir()) {
+        merge_case_variants_in(path, root, dropped)?;
+    }
 
-    Ok(OperationResult::success("目录已删除"))
+    Ok(())
 }
 
-/// 复制 MOD 文件到游戏目录
-#[tauri::command]
-pub fn copy_mod_to_game(
-    mod_path: String,
-    game_path: String,
-) -> Result<OperationResult, String> {
-    let mod_dir = Path::new(&mod_path);
-    let game_dir = Path::new(&game_path);
-
-    if !mod_dir.exists() {
-        return Err("MOD 目录不存在".to_string());
+/// 移动文件，跨文件系统时退化为复制后删除
+pub fn move_file(src: &Path, dst: &Path) -> io::Result<()> {
+    if let Some(parent) = dst.parent() {
+        fs::create_dir_all(parent)?;
     }
 
-    if !game_dir.exists() {
-        return Err("游戏目录不存在".to_string());
+    if fs::rename(src, dst).is_ok() {
+        return Ok(());
     }
 
-    // 复制 nativepc 文件夹
-    let nativepc_src = mod_dir.join("nativepc");
-    if nativepc_src.exists() {
-        let nativepc_dst = game_dir.join("nativepc");
-        copy_dir_recursive(&nativepc_src, &nativepc_dst)
-            .map_err(|e| format!("复制 nativepc 失败: {}", e))?;
+    fs::copy(src, dst)?;
+    fs::remove_file(src)
+}
+
+/// 按指定方式将文件放置到 `dst`，返回实际使用的方式
+///
+/// `dst` 不能已存在。链接失败时（如硬链接跨文件系统、Windows 下没有创建
+/// 符号链接的权限）退化为复制。
+pub fn place_file(src: &Path, dst: &Path, method: DeployMethod) -> io::Result<DeployMethod> {
+    let linked = match method {
+        DeployMethod::Copy => false,
+        DeployMethod::Hardlink => fs::hard_link(src, dst).is_ok(),
+        DeployMethod::Symlink => symlink_file(src, dst).is_ok(),
+    };
+    if linked {
+        return Ok(method);
     }
 
-    // 复制根目录下的其他文件
-    for entry in fs::read_dir(mod_dir).map_err(|e| format!("读取 MOD 目录失败: {}", e))? {
-        let entry = entry.map_err(|e| format!("读取目录项失败: {}", e))?;
-        let path = entry.path();
-        let file_name = entry.file_name();
+    copy_file(src, dst)?;
+    Ok(DeployMethod::Copy)
+}
 
-        // 跳过 nativepc 和 mod-info.json
-        if file_name == "nativepc" || file_name == "mod-info.json" {
-            continue;
-        }
+/// 复制文件，优先尝试 reflink
+///
+/// 在 btrfs、XFS 等支持写时复制的文件系统上，reflink 几乎瞬间完成且不占用
+/// 额外空间，同时仍是独立的文件，修改副本不会影响源文件。不支持时退化为
+/// 普通复制。返回复制的字节数。
+pub fn copy_file(src: &Path, dst: &Path) -> io::Result<u64> {
+    #[cfg(target_os = "linux")]
+    if reflink_file(src, dst).is_ok() {
+        return fs::metadata(dst).map(|m| m.len());
+    }
 
-        let dst_path = game_dir.join(&file_name);
+    fs::copy(src, dst)
+}
 
-        if path.is_dir() {
-            copy_dir_recursive(&path, &dst_path)
-                .map_err(|e| format!("复制目录 {} 失败: {}", file_name.to_string_lossy(), e))?;
-        } else {
-            fs::copy(&path, &dst_path)
-                .map_err(|e| format!("复制文件 {} 失败: {}", file_name.to_string_lossy(), e))?;
-        }
+/// 通过 FICLONE 创建 reflink，`dst` 必须不存在
+#[cfg(target_os = "linux")]
+fn reflink_file(src: &Path, dst: &Path) -> io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+
+    let source = fs::File::open(src)?;
+    let target = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(dst)?;
+
+    // SAFETY: 两个文件描述符在调用期间都保持打开
+    let result = unsafe { libc::ioctl(target.as_raw_fd(), libc::FICLONE, source.as_raw_fd()) };
+    if result == -1 {
+        let error = io::Error::last_os_error();
+        drop(target);
+        let _ = fs::remove_file(dst);
+        return Err(error);
     }
 
-    Ok(OperationResult::success("MOD 文件已复制到游戏目录"))
+    // 与 fs::copy 一致，保留源文件的权限
+    fs::set_permissions(dst, source.metadata()?.permissions())
 }
 
-/// 从游戏目录删除 MOD 文件
-#[tauri::command]
-pub fn remove_mod_from_game(
-    game_path: String,
-    nativepc_files: Vec<String>,
-    root_files: Vec<String>,
-) -> Result<OperationResult, String> {
-    let game_dir = Path::new(&game_path);
+#[cfg(unix)]
+fn symlink_file(src: &Path, dst: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(src, dst)
+}
 
-    if !game_dir.exists() {
-        return Err("游戏目录不存在".to_string());
+#[cfg(windows)]
+fn symlink_file(src: &Path, dst: &Path) -> io::Result<()> {
+    std::os::windows::fs::symlink_file(src, dst)
+}
+
+/// 路径上是否存在文件或链接（包括指向不存在文件的符号链接）
+pub fn path_exists(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok()
+}
+
+/// 在文件名后追加后缀，例如 `config.json` -> `config.json.bak`
+pub fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// 是否为管理器写在 MOD 目录中的元数据文件
+///
+/// 包括 `mod-info.json` 以及写入时产生的 `.bak` 备份和 `.tmp` 临时文件，
+/// 这些文件不属于 MOD 内容，不能部署到游戏目录。
+pub fn is_mod_metadata(file_name: &OsStr) -> bool {
+    file_name
+        .to_string_lossy()
+        .to_ascii_lowercase()
+        .starts_with("mod-info.json")
+}
+
+/// 原子地替换文件内容
+///
+/// 先写入同目录下的临时文件并刷新到磁盘，再重命名覆盖目标文件，中途崩溃时
+/// 目标文件要么是旧内容，要么是新内容。
+pub fn write_file_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let temp_path = with_suffix(path, ".tmp");
+
+    let result = (|| {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        fs::rename(&temp_path, path)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
     }
+    result
+}
+
+/// 递归删除目录中的特定文件
+pub fn remove_mod_files(game_dir: &Path, mod_files: &[String]) -> io::Result<()> {
+    for file_path in mod_files {
+        let full_path = game_dir.join(file_path);
 
-    // 删除 nativepc 中的文件
-    for file_path in nativepc_files {
-        let full_path = game_dir.join("nativepc").join(&file_path);
         if full_path.exists() {
             if full_path.is_dir() {
-                fs::remove_dir_all(&full_path)
-                    .map_err(|e| format!("删除目录 {} 失败: {}", file_path, e))?;
+                fs::remove_dir_all(&full_path)?;
             } else {
-                fs::remove_file(&full_path)
-                    .map_err(|e| format!("删除文件 {} 失败: {}", file_path, e))?;
+                fs::remove_file(&full_path)?;
             }
         }
     }
 
-    // 删除根目录中的文件
-    for file_path in root_files {
-        let full_path = game_dir.join(&file_path);
-        if full_path.exists() {
-            if full_path.is_dir() {
-                fs::remove_dir_all(&full_path)
-                    .map_err(|e| format!("删除目录 {} 失败: {}", file_path, e))?;
-            } else {
-                fs::remove_file(&full_path)
-                    .map_err(|e| format!("删除文件 {} 失败: {}", file_path, e))?;
-            }
+    Ok(())
+}
+
+/// 计算文件内容哈希（blake3，十六进制）
+pub fn hash_file(path: &Path) -> io::Result<String> {
+    let mut hasher = blake3::Hasher::new();
+    let mut file = fs::File::open(path)?;
+    io::copy(&mut file, &mut hasher)?;
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+/// 递归列出目录中的所有文件
+pub fn list_files_recursive(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(list_files_recursive(&path)?);
+        } else {
+            files.push(path);
         }
     }
 
-    Ok(OperationResult::success("MOD 文件已从游戏目录删除"))
+    Ok(files)
+}
+
+/// 删除 `dir` 下所有空的子目录（`dir` 本身保留），返回删除的目录数
+pub fn prune_empty_dirs(dir: &Path) -> io::Result<usize> {
+    let mut removed = 0;
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !fs::symlink_metadata(&path)?.is_dir() {
+            continue;
+        }
+
+        removed += prune_empty_dirs(&path)?;
+        if fs::read_dir(&path)?.next().is_none() {
+            fs::remove_dir(&path)?;
+            removed += 1;
+        }
+    }
+
+    Ok(removed)
+}
+
+/// 完全删除目录
+#[tauri::command]
+pub fn delete_directory(path: String) -> Result<OperationResult, String> {
+    let dir_path = Path::new(&path);
+
+    if !dir_path.exists() {
+        return Err("目录不存在".to_string());
+    }
+
+    fs::remove_dir_all(dir_path)
+        .map_err(|e| format!("删除目录失败: {}", e))?;
+
+    Ok(OperationResult::success("目录已删除"))
 }
 
 /// 获取目录大小
@@ -0,0 +1,194 @@
+use crate::models::{FileProgress, OperationResult};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, TryLockError};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// 进度事件名称
+pub const PROGRESS_EVENT: &str = "file-progress";
+
+/// 两次进度事件之间的最短间隔，避免大量小文件时刷屏
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 正在运行的后台任务（通过 `app.manage` 注册）
+#[derive(Default)]
+pub struct JobRegistry {
+    jobs: Mutex<HashMap<String, Arc<AtomicBool>>>,
+    /// 数据锁：修改配置、部署清单、MOD 目录或游戏目录的操作都必须持有，
+    /// 避免并发的操作读到过期的配置后互相覆盖
+    data: Mutex<()>,
+}
+
+/// 获取数据锁，其他操作持有时等待（用于后台任务）
+pub fn lock_data(app: &AppHandle) -> MutexGuard<'_, ()> {
+    // 锁只保护 `()`，持有者 panic 后也可以安全地继续使用
+    app.state::<JobRegistry>()
+        .inner()
+        .data
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// 尝试获取数据锁，其他操作正在进行时直接返回错误（用于同步命令，避免阻塞界面）
+pub fn try_lock_data(app: &AppHandle) -> Result<MutexGuard<'_, ()>, String> {
+    match app.state::<JobRegistry>().inner().data.try_lock() {
+        Ok(guard) => Ok(guard),
+        Err(TryLockError::Poisoned(e)) => Ok(e.into_inner()),
+        Err(TryLockError::WouldBlock) => Err("有其他操作正在进行，请稍后再试".to_string()),
+    }
+}
+
+/// 后台任务：发送 `FileProgress` 事件并响应取消请求
+///
+/// 任务结束（被 drop）时自动从注册表中移除。
+pub struct Job {
+    app: Option<AppHandle>,
+    id: String,
+    cancelled: Arc<AtomicBool>,
+    progress: Mutex<(FileProgress, Option<Instant>)>,
+}
+
+impl Job {
+    /// 创建并注册任务，未指定 `id` 时自动生成
+    pub fn start(app: &AppHandle, id: Option<String>) -> Self {
+        let id = id.unwrap_or_else(|| {
+            format!(
+                "job-{}",
+                chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+            )
+        });
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        if let Ok(mut jobs) = app.state::<JobRegistry>().jobs.lock() {
+            jobs.insert(id.clone(), cancelled.clone());
+        }
+
+        Self {
+            app: Some(app.clone()),
+            progress: Mutex::new((FileProgress::new(&id), None)),
+            id,
+            cancelled,
+        }
+    }
+
+    /// 不发送事件、不可取消的任务，用于回滚等内部操作
+    pub fn detached() -> Self {
+        Self {
+            app: None,
+            id: String::new(),
+            cancelled: Arc::new(AtomicBool::new(false)),
+            progress: Mutex::new((FileProgress::new(""), None)),
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// 已请求取消时返回错误，用于在文件之间中止操作
+    pub fn check_cancelled(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            Err("操作已取消".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// 开始新的阶段，重置进度
+    pub fn begin(&self, total: usize, total_bytes: u64) {
+        if let Ok(mut progress) = self.progress.lock() {
+            progress.0 = FileProgress {
+                total,
+                total_bytes,
+                ..FileProgress::new(&self.id)
+            };
+            progress.1 = None;
+        }
+        self.emit(true);
+    }
+
+    /// 记录处理完一个文件
+    pub fn advance(&self, file: &str, bytes: u64) {
+        if let Ok(mut progress) = self.progress.lock() {
+            let p = &mut progress.0;
+            p.current += 1;
+            p.bytes += bytes;
+            p.current_file = Some(file.to_string());
+            p.percentage = if p.total_bytes > 0 {
+                p.bytes as f32 / p.total_bytes as f32 * 100.0
+            } else if p.total > 0 {
+                p.current as f32 / p.total as f32 * 100.0
+            } else {
+                100.0
+            };
+        }
+        self.emit(false);
+    }
+
+    /// 发送进度事件；`force` 为 false 时按 `PROGRESS_INTERVAL` 节流
+    fn emit(&self, force: bool) {
+        let Some(app) = &self.app else {
+            return;
+        };
+        let Ok(mut progress) = self.progress.lock() else {
+            return;
+        };
+
+        let done = progress.0.current >= progress.0.total;
+        let due = progress
+            .1
+            .is_none_or(|last| last.elapsed() >= PROGRESS_INTERVAL);
+        if force || done || due {
+            progress.1 = Some(Instant::now());
+            let _ = app.emit(PROGRESS_EVENT, &progress.0);
+        }
+    }
+}
+
+impl Drop for Job {
+    fn drop(&mut self) {
+        if let Some(app) = &self.app {
+            if let Ok(mut jobs) = app.state::<JobRegistry>().jobs.lock() {
+                jobs.remove(&self.id);
+            }
+        }
+    }
+}
+
+/// 在后台线程中运行任务，避免阻塞界面
+///
+/// 任务持有数据锁运行，同时发起的多个任务会依次执行。
+pub async fn run_job<T, F>(app: AppHandle, job_id: Option<String>, work: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&AppHandle, &Job) -> Result<T, String> + Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(move || {
+        let job = Job::start(&app, job_id);
+        let _data = lock_data(&app);
+        work(&app, &job)
+    })
+    .await
+    .map_err(|e| format!("后台任务执行失败: {}", e))?
+}
+
+/// 请求取消正在运行的任务，已完成的部分会被回滚
+#[tauri::command]
+pub fn cancel_job(
+    registry: State<'_, JobRegistry>,
+    job_id: String,
+) -> Result<OperationResult, String> {
+    let jobs = registry
+        .jobs
+        .lock()
+        .map_err(|_| "任务注册表已损坏".to_string())?;
+
+    match jobs.get(&job_id) {
+        Some(cancelled) => {
+            cancelled.store(true, Ordering::SeqCst);
+            Ok(OperationResult::success("已请求取消"))
+        }
+        None => Err(format!("任务不存在或已结束: {}", job_id)),
+    }
+}
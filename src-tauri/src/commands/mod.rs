@@ -1,11 +1,23 @@
 pub mod config;
+pub mod conflicts;
 pub mod dialog;
 pub mod archive;
 pub mod file_ops;
+pub mod jobs;
 pub mod mod_manager;
+pub mod naming;
+pub mod profiles;
+pub mod untracked;
+pub mod verify;
 
 pub use config::*;
+pub use conflicts::*;
 pub use dialog::*;
 pub use archive::*;
 pub use file_ops::*;
+pub use jobs::*;
 pub use mod_manager::*;
+pub use naming::*;
+pub use profiles::*;
+pub use untracked::*;
+pub use verify::*;
@@ -1,10 +1,23 @@
 use crate::commands::{
-    archive::{calculate_dir_size, extract_zip_archive},
-    config::{get_data_dir, load_config, save_config},
+    archive::{calculate_dir_size, extract_archive_with_job},
+    config::{
+        get_backup_dir, get_data_dir, get_staging_dir, load_config, load_manifest, write_config,
+        save_manifest, write_json_file,
+    },
+    file_ops::{
+        case_insensitive_key, find_entry_case_insensitive, is_mod_metadata, move_file,
+        path_exists, place_file, prune_empty_dirs, resolve_case_insensitive,
+    },
+    jobs::{run_job, try_lock_data, Job},
+    naming::{is_mod_name_taken, validate_existing_mod_name, validate_mod_name},
 };
-use crate::models::{ModConfigItem, ModFiles, ModInfo, OperationResult};
+use crate::models::{
+    AppConfig, DeployMethod, DeployedFile, DeploymentManifest, ModAction, ModChange,
+    ModChangeResult, ModConfigItem, ModFiles, ModInfo, OperationResult,
+};
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tauri::AppHandle;
 
 /// 收集 MOD 文件列表
@@ -23,8 +36,8 @@ fn collect_mod_files(mod_dir: &Path) -> Result<ModFiles, String> {
         let entry = entry.map_err(|e| format!("读取目录项失败: {}", e))?;
         let file_name = entry.file_name();
 
-        // 跳过 nativepc 和 mod-info.json
-        if file_name == "nativepc" || file_name == "mod-info.json" {
+        // 跳过 nativepc 和 MOD 元数据文件
+        if file_name == "nativepc" || is_mod_metadata(&file_name) {
             continue;
         }
 
@@ -70,111 +83,529 @@ fn collect_files_recursive(
 }
 
 /// 完整的 MOD 安装流程
+///
+/// `install_roots` 为要安装的变体根目录（见 `preview_archive`），按顺序合并，
+/// 排在前面的优先；未指定时自动选择。
+/// 解压进度通过 `file-progress` 事件发送，可用 `cancel_job(job_id)` 取消。
 #[tauri::command]
-pub fn install_mod(
+pub async fn install_mod(
     app: AppHandle,
     archive_path: String,
     mod_name: String,
     nexus_id: Option<String>,
     categories: Vec<String>,
+    install_roots: Option<Vec<String>>,
+    job_id: Option<String>,
 ) -> Result<OperationResult, String> {
-    // 1. 验证 MOD 名称是否已存在
+    run_job(app, job_id, move |app, job| {
+        install_mod_with_job(
+            app,
+            &archive_path,
+            mod_name,
+            nexus_id,
+            categories,
+            install_roots,
+            job,
+        )
+    })
+    .await
+}
+
+fn install_mod_with_job(
+    app: &AppHandle,
+    archive_path: &str,
+    mod_name: String,
+    nexus_id: Option<String>,
+    categories: Vec<String>,
+    install_roots: Option<Vec<String>>,
+    job: &Job,
+) -> Result<OperationResult, String> {
+    // 1. 验证 MOD 名称是否有效、是否已存在
+    validate_mod_name(&mod_name)?;
     let config = load_config(app.clone())?;
-    if config.mods.iter().any(|m| m.name == mod_name) {
+    if is_mod_name_taken(&config, &mod_name) {
         return Err(format!("MOD \"{}\" 已存在", mod_name));
     }
 
-    // 2. 获取数据目录并创建 MOD 目录
-    let data_dir = get_data_dir(&app)?;
+    // 2. 获取数据目录，在暂存目录中准备 MOD
+    let data_dir = get_data_dir(app)?;
     let mod_dir = data_dir.join(&mod_name);
 
     if mod_dir.exists() {
         return Err(format!("MOD 目录已存在: {:?}", mod_dir));
     }
 
-    fs::create_dir_all(&mod_dir).map_err(|e| format!("创建 MOD 目录失败: {}", e))?;
+    let staging_dir = get_staging_dir(app)?.join(&mod_name);
+    if staging_dir.exists() {
+        // 上次安装中断时残留的暂存目录
+        fs::remove_dir_all(&staging_dir)
+            .map_err(|e| format!("清理暂存目录失败: {}", e))?;
+    }
+    fs::create_dir_all(&staging_dir).map_err(|e| format!("创建暂存目录失败: {}", e))?;
+
+    // 3. 解压压缩包到暂存目录
+    let result = extract_archive_with_job(
+        Path::new(archive_path),
+        &staging_dir,
+        install_roots,
+        job,
+    )
+    .and_then(|extract_result| {
+        let mod_info = ModInfo {
+            name: mod_name.clone(),
+            nexus_id,
+            categories,
+            enabled: false,
+            install_date: chrono::Utc::now().to_rfc3339(),
+            file_size: 0,
+            files: ModFiles::default(),
+        };
+        commit_staged_mod(app, &staging_dir, &mod_dir, config, mod_info, job)?;
+
+        let message = if extract_result.skipped.is_empty() {
+            format!("MOD \"{}\" 安装成功", mod_name)
+        } else {
+            format!(
+                "MOD \"{}\" 安装成功，已跳过 {} 个不安全或重名的条目",
+                mod_name,
+                extract_result.skipped.len()
+            )
+        };
+        Ok(OperationResult::success(message).with_skipped(extract_result.skipped))
+    });
 
-    // 3. 解压压缩包到 MOD 目录
-    extract_zip_archive(archive_path, mod_dir.to_string_lossy().to_string())?;
+    // 任何一步失败都不在数据目录中留下半成品
+    if result.is_err() && staging_dir.exists() {
+        let _ = fs::remove_dir_all(&staging_dir);
+    }
+    result
+}
 
+/// 在准备好文件的暂存目录中写入 mod-info.json，更新 config.json（新 MOD 排在
+/// 最后、未启用）后再整体移动到 MOD 目录
+pub fn commit_staged_mod(
+    app: &AppHandle,
+    staging_dir: &Path,
+    mod_dir: &Path,
+    config: AppConfig,
+    mut mod_info: ModInfo,
+    job: &Job,
+) -> Result<(), String> {
     // 4. 收集 MOD 文件列表
-    let mod_files = collect_mod_files(&mod_dir)?;
+    mod_info.files = collect_mod_files(staging_dir)?;
 
     // 5. 计算 MOD 大小
-    let file_size = calculate_dir_size(&mod_dir).unwrap_or(0);
+    mod_info.file_size = calculate_dir_size(staging_dir).unwrap_or(0);
 
     // 6. 创建 mod-info.json
-    let mod_info = ModInfo {
-        name: mod_name.clone(),
-        nexus_id,
-        categories,
-        enabled: false,
-        install_date: chrono::Utc::now().to_rfc3339(),
-        file_size,
-        files: mod_files,
-    };
+    write_json_file(&staging_dir.join("mod-info.json"), &mod_info, " MOD 信息")?;
 
-    let mod_info_path = mod_dir.join("mod-info.json");
-    let mod_info_content =
-        serde_json::to_string_pretty(&mod_info).map_err(|e| format!("序列化失败: {}", e))?;
-    fs::write(&mod_info_path, mod_info_content).map_err(|e| format!("写入文件失败: {}", e))?;
+    job.check_cancelled()?;
 
     // 7. 更新 config.json
-    let mut new_config = config;
+    let mut new_config = config.clone();
     let order = new_config.mods.len() + 1;
     new_config.mods.push(ModConfigItem {
-        name: mod_name.clone(),
+        name: mod_info.name.clone(),
         order,
         enabled: false,
     });
 
-    save_config(app, new_config)?;
+    write_config(app, &new_config)?;
 
-    Ok(OperationResult::success(format!(
-        "MOD \"{}\" 安装成功",
-        mod_name
-    )))
+    // 8. 将暂存目录移动到 MOD 目录，失败时撤销配置修改
+    if let Err(e) = fs::rename(staging_dir, mod_dir) {
+        write_config(app, &config)?;
+        return Err(format!("移动 MOD 目录失败: {}", e));
+    }
+
+    Ok(())
 }
 
-/// 启用 MOD（复制到游戏目录）
-#[tauri::command]
-pub fn enable_mod(app: AppHandle, mod_name: String) -> Result<OperationResult, String> {
-    use crate::commands::config::{load_config, save_mod_info};
-    use crate::commands::file_ops::copy_mod_to_game;
+/// 部署计划中的单个文件
+#[derive(Debug, Clone)]
+pub struct PlannedFile {
+    /// 提供该文件的 MOD 名称
+    pub owner: String,
+    /// MOD 中的相对路径（以 `/` 分隔，保留原始大小写）
+    pub target: String,
+    /// 数据目录中的源文件路径
+    pub source: PathBuf,
+}
 
-    // 1. 加载配置
-    let config = load_config(app.clone())?;
+/// 部署计划：小写的相对路径键（见 [`case_insensitive_key`]）-> 胜出的 MOD 文件
+pub type DeployPlan = BTreeMap<String, PlannedFile>;
 
-    // 2. 获取 MOD 目录
-    let data_dir = get_data_dir(&app)?;
-    let mod_dir = data_dir.join(&mod_name);
+/// 将相对路径转换为以 `/` 分隔的字符串
+pub fn relative_key(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// 列出 MOD 需要部署到游戏目录的所有文件（目标相对路径, 源文件路径）
+pub fn collect_deploy_files(mod_dir: &Path) -> Result<Vec<(String, PathBuf)>, String> {
+    let mut files = Vec::new();
+
+    for entry in fs::read_dir(mod_dir).map_err(|e| format!("读取目录失败: {}", e))? {
+        let entry = entry.map_err(|e| format!("读取目录项失败: {}", e))?;
+        if is_mod_metadata(&entry.file_name()) {
+            continue;
+        }
+
+        let path = entry.path();
+        if path.is_dir() {
+            let mut nested = Vec::new();
+            collect_files_recursive(&path, mod_dir, &mut nested)?;
+            files.extend(
+                nested
+                    .into_iter()
+                    .map(|relative| (relative_key(Path::new(&relative)), mod_dir.join(&relative))),
+            );
+        } else {
+            files.push((entry.file_name().to_string_lossy().to_string(), path));
+        }
+    }
 
-    if !mod_dir.exists() {
-        return Err(format!("MOD 目录不存在: {}", mod_name));
+    Ok(files)
+}
+
+/// 根据配置计算部署计划
+///
+/// 按 `order` 从小到大遍历 MOD，同一路径（忽略大小写）只保留排序最靠前的
+/// MOD 提供的文件。
+/// `include_disabled` 为 true 时同时计入未启用的 MOD。
+pub fn build_deploy_plan(
+    data_dir: &Path,
+    config: &AppConfig,
+    include_disabled: bool,
+) -> Result<DeployPlan, String> {
+    let mut mods: Vec<&ModConfigItem> = config
+        .mods
+        .iter()
+        .filter(|m| include_disabled || m.enabled)
+        .collect();
+    mods.sort_by_key(|m| m.order);
+
+    let mut plan = DeployPlan::new();
+    for mod_item in mods {
+        let mod_dir = data_dir.join(&mod_item.name);
+        if !mod_dir.exists() {
+            return Err(format!("MOD 目录不存在: {}", mod_item.name));
+        }
+
+        for (target, source) in collect_deploy_files(&mod_dir)? {
+            plan.entry(case_insensitive_key(&target))
+                .or_insert_with(|| PlannedFile {
+                    owner: mod_item.name.clone(),
+                    target,
+                    source,
+                });
+        }
     }
 
-    // 3. 复制文件到游戏目录
-    copy_mod_to_game(
-        mod_dir.to_string_lossy().to_string(),
-        config.game_directory.clone(),
-    )?;
+    Ok(plan)
+}
+
+/// 部署操作涉及的目录
+pub struct DeployContext {
+    /// 游戏主目录
+    pub game_dir: PathBuf,
+    /// 原始游戏文件的备份目录
+    pub backup_dir: PathBuf,
+    /// 部署方式
+    pub method: DeployMethod,
+}
 
-    // 4. 更新 mod-info.json
-    let mod_info_path = mod_dir.join("mod-info.json");
-    let mut mod_info: ModInfo = serde_json::from_str(
-        &fs::read_to_string(&mod_info_path).map_err(|e| format!("读取文件失败: {}", e))?,
-    )
-    .map_err(|e| format!("解析失败: {}", e))?;
+impl DeployContext {
+    pub fn new(app: &AppHandle, config: &AppConfig) -> Result<Self, String> {
+        Ok(Self {
+            game_dir: PathBuf::from(&config.game_directory),
+            backup_dir: get_backup_dir(app)?,
+            method: config.settings.deploy_method,
+        })
+    }
+}
+
+/// 将单个文件部署到游戏目录并记录到清单
+///
+/// 目标路径按大小写不敏感的方式解析，沿用游戏目录中已有的写法。如果目标路径
+/// 尚未被管理且已存在文件（原版或用户文件），先将其备份。
+/// 按 `ctx.method` 部署，清单中记录实际使用的方式。
+pub fn deploy_file(
+    ctx: &DeployContext,
+    key: &str,
+    file: &PlannedFile,
+    manifest: &mut DeploymentManifest,
+) -> Result<(), String> {
+    let full_path = match manifest.files.get(key) {
+        Some(deployed) => ctx.game_dir.join(&deployed.path),
+        None => resolve_case_insensitive(&ctx.game_dir, Path::new(&file.target)),
+    };
+    let path = full_path
+        .strip_prefix(&ctx.game_dir)
+        .map(relative_key)
+        .unwrap_or_else(|_| file.target.clone());
+
+    if !manifest.files.contains_key(key) && path_exists(&full_path) && !full_path.is_dir() {
+        move_file(&full_path, &ctx.backup_dir.join(key))
+            .map_err(|e| format!("备份原始文件 {} 失败: {}", path, e))?;
+        manifest.backups.insert(key.to_string());
+    }
+
+    // 先删除之前部署的文件：它可能是指向其他 MOD 源文件的链接，直接覆盖
+    // 会改写该 MOD 的文件
+    if path_exists(&full_path) {
+        fs::remove_file(&full_path).map_err(|e| format!("删除文件 {} 失败: {}", path, e))?;
+    }
+
+    if let Some(parent) = full_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("创建目录 {} 失败: {}", parent.display(), e))?;
+    }
+    let method = place_file(&file.source, &full_path, ctx.method)
+        .map_err(|e| format!("复制文件 {} 失败: {}", path, e))?;
+    let size = fs::metadata(&full_path)
+        .map_err(|e| format!("读取文件 {} 信息失败: {}", path, e))?
+        .len();
+
+    // 部署时不计算哈希，避免读取整个文件（链接和克隆本身几乎不需要时间）
+    manifest.files.insert(
+        key.to_string(),
+        DeployedFile {
+            path,
+            owner: file.owner.clone(),
+            hash: String::new(),
+            size,
+            method,
+        },
+    );
+
+    Ok(())
+}
+
+/// 从游戏目录移除已部署的文件，有备份时恢复原始文件
+pub fn undeploy_file(
+    ctx: &DeployContext,
+    key: &str,
+    manifest: &mut DeploymentManifest,
+) -> Result<(), String> {
+    let Some(deployed) = manifest.files.remove(key) else {
+        return Ok(());
+    };
+
+    let full_path = ctx.game_dir.join(&deployed.path);
+    if path_exists(&full_path) && !full_path.is_dir() {
+        if let Err(e) = fs::remove_file(&full_path) {
+            manifest.files.insert(key.to_string(), deployed.clone());
+            return Err(format!("删除文件 {} 失败: {}", deployed.path, e));
+        }
+    }
+
+    if manifest.backups.contains(key) {
+        move_file(&ctx.backup_dir.join(key), &full_path)
+            .map_err(|e| format!("恢复原始文件 {} 失败: {}", deployed.path, e))?;
+        manifest.backups.remove(key);
+    }
+
+    Ok(())
+}
+
+/// 将游戏目录同步到部署计划，并更新部署清单
+///
+/// 移除清单中不再被任何 MOD 提供的文件（并恢复其备份），复制胜出 MOD
+/// 发生变化或已缺失的文件。`force` 为 true 时无论归属是否变化都重新复制。
+/// 每处理一个文件报告一次进度；中途失败或被取消时，`manifest` 仍然准确反映
+/// 已完成的操作。
+pub fn apply_deploy_plan(
+    ctx: &DeployContext,
+    manifest: &mut DeploymentManifest,
+    plan: &DeployPlan,
+    force: bool,
+    job: &Job,
+) -> Result<(), String> {
+    if !ctx.game_dir.exists() {
+        return Err("游戏目录不存在".to_string());
+    }
+
+    let stale: Vec<String> = manifest
+        .files
+        .keys()
+        .filter(|k| !plan.contains_key(*k))
+        .cloned()
+        .collect();
+    let changed: Vec<(&String, &PlannedFile)> = plan
+        .iter()
+        .filter(|(key, file)| {
+            force
+                || !manifest.files.get(*key).is_some_and(|deployed| {
+                    deployed.owner == file.owner && ctx.game_dir.join(&deployed.path).exists()
+                })
+        })
+        .collect();
+
+    let total_bytes = changed
+        .iter()
+        .map(|(_, file)| fs::metadata(&file.source).map_or(0, |m| m.len()))
+        .sum();
+    job.begin(stale.len() + changed.len(), total_bytes);
+
+    // 1. 移除不再被任何 MOD 提供的文件
+    for key in stale {
+        job.check_cancelled()?;
+        undeploy_file(ctx, &key, manifest)?;
+        job.advance(&key, 0);
+    }
+
+    // 2. 复制胜出 MOD 发生变化的文件
+    for (key, file) in changed {
+        job.check_cancelled()?;
+        deploy_file(ctx, key, file, manifest)?;
+        job.advance(&file.target, manifest.files.get(key).map_or(0, |f| f.size));
+    }
+
+    Ok(())
+}
+
+/// 加载部署清单
+///
+/// 旧版本没有部署清单，此时认为 `config` 中已启用的 MOD 已按计划部署，
+/// 以游戏目录中现有的文件生成初始清单。
+pub fn load_or_seed_manifest(
+    app: &AppHandle,
+    data_dir: &Path,
+    config: &AppConfig,
+) -> Result<DeploymentManifest, String> {
+    if let Some(manifest) = load_manifest(app)? {
+        return Ok(manifest);
+    }
+
+    let game_dir = Path::new(&config.game_directory);
+    let mut manifest = DeploymentManifest::default();
+
+    for (key, file) in build_deploy_plan(data_dir, config, false)? {
+        let full_path = resolve_case_insensitive(game_dir, Path::new(&file.target));
+        if !full_path.is_file() {
+            continue;
+        }
+        let target = full_path
+            .strip_prefix(game_dir)
+            .map(relative_key)
+            .unwrap_or_else(|_| file.target.clone());
+
+        let size = fs::metadata(&full_path)
+            .map_err(|e| format!("读取文件 {} 信息失败: {}", target, e))?
+            .len();
+        manifest.files.insert(
+            key,
+            DeployedFile {
+                path: target,
+                owner: file.owner,
+                hash: String::new(),
+                size,
+                method: DeployMethod::Copy,
+            },
+        );
+    }
+
+    Ok(manifest)
+}
+
+/// 将游戏目录同步到 `new_config` 描述的部署状态
+///
+/// `current_config` 为修改前的配置，仅在首次生成部署清单时使用。
+pub fn sync_deployment(
+    app: &AppHandle,
+    data_dir: &Path,
+    current_config: &AppConfig,
+    new_config: &AppConfig,
+    force: bool,
+    job: &Job,
+) -> Result<(), String> {
+    let mut manifest = load_or_seed_manifest(app, data_dir, current_config)?;
+    let plan = build_deploy_plan(data_dir, new_config, false)?;
+
+    let ctx = DeployContext::new(app, new_config)?;
+
+    let result = apply_deploy_plan(&ctx, &mut manifest, &plan, force, job);
+
+    // 无论成功与否都保存清单，保证其与游戏目录一致
+    save_manifest(app, &manifest)?;
+    result
+}
+
+/// 将配置从 `config` 切换到 `new_config`
+///
+/// 只同步部署发生变化的文件，更新启用状态改变的 MOD 的 mod-info.json，最后
+/// 保存 config.json。被取消时将游戏目录恢复到修改前的部署状态。
+pub fn apply_config_change(
+    app: &AppHandle,
+    config: &AppConfig,
+    new_config: AppConfig,
+    job: &Job,
+) -> Result<(), String> {
+    use crate::commands::config::{load_mod_info, write_mod_info};
+
+    // 1. 按排序同步游戏目录
+    let data_dir = get_data_dir(app)?;
+    if let Err(e) = sync_deployment(app, &data_dir, config, &new_config, false, job) {
+        if job.is_cancelled() {
+            sync_deployment(app, &data_dir, config, config, false, &Job::detached())
+                .map_err(|rollback| format!("{}，且回滚失败: {}", e, rollback))?;
+        }
+        return Err(e);
+    }
 
-    mod_info.enabled = true;
-    save_mod_info(app.clone(), mod_name.clone(), mod_info)?;
+    // 2. 更新启用状态改变的 MOD 的 mod-info.json
+    for mod_item in &new_config.mods {
+        let was_enabled = config
+            .mods
+            .iter()
+            .find(|m| m.name == mod_item.name)
+            .is_some_and(|m| m.enabled);
+        if was_enabled == mod_item.enabled {
+            continue;
+        }
 
-    // 5. 更新 config.json
-    let mut new_config = config;
-    if let Some(mod_item) = new_config.mods.iter_mut().find(|m| m.name == mod_name) {
-        mod_item.enabled = true;
+        let mut mod_info = load_mod_info(app.clone(), mod_item.name.clone())?;
+        mod_info.enabled = mod_item.enabled;
+        write_mod_info(app, &mod_item.name, &mod_info)?;
     }
-    save_config(app, new_config)?;
+
+    // 3. 更新 config.json
+    write_config(app, &new_config)?;
+
+    Ok(())
+}
+
+/// 修改 MOD 启用状态并重新计算部署
+fn set_mod_enabled(
+    app: &AppHandle,
+    mod_name: &str,
+    enabled: bool,
+    job: &Job,
+) -> Result<(), String> {
+    let config = load_config(app.clone())?;
+
+    let mut new_config = config.clone();
+    match new_config.mods.iter_mut().find(|m| m.name == mod_name) {
+        Some(mod_item) => mod_item.enabled = enabled,
+        None => return Err(format!("MOD \"{}\" 不存在", mod_name)),
+    }
+
+    apply_config_change(app, &config, new_config, job)
+}
+
+/// 启用 MOD（按排序部署到游戏目录）
+#[tauri::command]
+pub async fn enable_mod(
+    app: AppHandle,
+    mod_name: String,
+    job_id: Option<String>,
+) -> Result<OperationResult, String> {
+    let name = mod_name.clone();
+    run_job(app, job_id, move |app, job| set_mod_enabled(app, &name, true, job)).await?;
 
     Ok(OperationResult::success(format!(
         "MOD \"{}\" 已启用",
@@ -182,71 +613,354 @@ pub fn enable_mod(app: AppHandle, mod_name: String) -> Result<OperationResult, S
     )))
 }
 
-/// 禁用 MOD（从游戏目录删除）
+/// 禁用 MOD（从游戏目录移除，并恢复其他 MOD 提供的同名文件）
 #[tauri::command]
-pub fn disable_mod(app: AppHandle, mod_name: String) -> Result<OperationResult, String> {
-    use crate::commands::config::{get_data_dir, load_config, load_mod_info, save_mod_info};
-    use crate::commands::file_ops::remove_mod_from_game;
+pub async fn disable_mod(
+    app: AppHandle,
+    mod_name: String,
+    job_id: Option<String>,
+) -> Result<OperationResult, String> {
+    let name = mod_name.clone();
+    run_job(app, job_id, move |app, job| set_mod_enabled(app, &name, false, job)).await?;
 
-    // 1. 加载配置和 MOD 信息
-    let config = load_config(app.clone())?;
-    let mut mod_info = load_mod_info(app.clone(), mod_name.clone())?;
+    Ok(OperationResult::success(format!(
+        "MOD \"{}\" 已禁用",
+        mod_name
+    )))
+}
 
-    // 2. 从游戏目录删除文件
-    remove_mod_from_game(
-        config.game_directory.clone(),
-        mod_info.files.nativepc.clone(),
-        mod_info.files.root.clone(),
-    )?;
+/// 按当前配置完整重新部署所有已启用的 MOD
+#[tauri::command]
+pub async fn redeploy_all(
+    app: AppHandle,
+    job_id: Option<String>,
+) -> Result<OperationResult, String> {
+    let file_count = run_job(app, job_id, |app, job| {
+        let config = load_config(app.clone())?;
+        let data_dir = get_data_dir(app)?;
 
-    // 3. 更新 mod-info.json
-    mod_info.enabled = false;
-    save_mod_info(app.clone(), mod_name.clone(), mod_info)?;
+        sync_deployment(app, &data_dir, &config, &config, true, job)?;
 
-    // 4. 更新 config.json
-    let mut new_config = config;
-    if let Some(mod_item) = new_config.mods.iter_mut().find(|m| m.name == mod_name) {
-        mod_item.enabled = false;
-    }
-    save_config(app, new_config)?;
+        Ok(load_manifest(app)?.map_or(0, |m| m.files.len()))
+    })
+    .await?;
+    Ok(OperationResult::success(format!(
+        "已重新部署 {} 个文件",
+        file_count
+    )))
+}
+
+/// 清除部署：将游戏目录恢复到未安装 MOD 的状态
+///
+/// 移除所有由管理器部署的文件并恢复被覆盖的原始文件，删除 nativepc 中因此
+/// 变空的子目录。配置中的启用状态保持不变，之后可通过 `redeploy_all` 恢复。
+#[tauri::command]
+pub async fn purge_deployment(
+    app: AppHandle,
+    job_id: Option<String>,
+) -> Result<OperationResult, String> {
+    let (file_count, dir_count) = run_job(app, job_id, |app, job| {
+        let config = load_config(app.clone())?;
+        let data_dir = get_data_dir(app)?;
+
+        // 1. 移除所有已部署的文件并恢复备份
+        let mut manifest = load_or_seed_manifest(app, &data_dir, &config)?;
+        let file_count = manifest.files.len();
+        let ctx = DeployContext::new(app, &config)?;
+        let result = apply_deploy_plan(&ctx, &mut manifest, &DeployPlan::new(), false, job);
+        save_manifest(app, &manifest)?;
+        result?;
+
+        // 2. 删除 nativepc 中的空目录
+        let dir_count = match find_entry_case_insensitive(&ctx.game_dir, "nativepc") {
+            Some(nativepc) => prune_empty_dirs(&nativepc)
+                .map_err(|e| format!("删除空目录失败: {}", e))?,
+            None => 0,
+        };
+
+        Ok((file_count, dir_count))
+    })
+    .await?;
 
     Ok(OperationResult::success(format!(
-        "MOD \"{}\" 已禁用",
-        mod_name
+        "已移除 {} 个文件，删除 {} 个空目录",
+        file_count, dir_count
     )))
 }
 
+/// 从配置（包括各方案）中移除 MOD，并将剩余 MOD 的 `order` 重新编号
+fn remove_mod_from_config(config: &mut AppConfig, mod_name: &str) {
+    config.mods.retain(|m| m.name != mod_name);
+    config.mods.sort_by_key(|m| m.order);
+    for (index, mod_item) in config.mods.iter_mut().enumerate() {
+        mod_item.order = index + 1;
+    }
+
+    for profile in &mut config.profiles {
+        profile.mods.retain(|m| m.name != mod_name);
+    }
+}
+
 /// 删除 MOD（完全删除）
 #[tauri::command]
 pub fn delete_mod(app: AppHandle, mod_name: String) -> Result<OperationResult, String> {
-    use crate::commands::config::{get_data_dir, load_config, load_mod_info};
-    use crate::commands::file_ops::{delete_directory, remove_mod_from_game};
+    use crate::commands::file_ops::delete_directory;
 
-    // 1. 加载配置和 MOD 信息
+    // 1. 加载配置
+    let _data = try_lock_data(&app)?;
+    validate_existing_mod_name(&mod_name)?;
     let config = load_config(app.clone())?;
-    let mod_info = load_mod_info(app.clone(), mod_name.clone())?;
+    let data_dir = get_data_dir(&app)?;
 
-    // 2. 如果 MOD 已启用，先从游戏目录删除
-    if mod_info.enabled {
-        remove_mod_from_game(
-            config.game_directory.clone(),
-            mod_info.files.nativepc.clone(),
-            mod_info.files.root.clone(),
-        )?;
-    }
+    let mut new_config = config.clone();
+    remove_mod_from_config(&mut new_config, &mod_name);
+
+    // 2. 从游戏目录移除该 MOD 的文件，并恢复被其覆盖的文件
+    sync_deployment(&app, &data_dir, &config, &new_config, false, &Job::detached())?;
 
     // 3. 删除 MOD 目录
-    let data_dir = get_data_dir(&app)?;
     let mod_dir = data_dir.join(&mod_name);
     delete_directory(mod_dir.to_string_lossy().to_string())?;
 
     // 4. 更新 config.json
-    let mut new_config = config;
-    new_config.mods.retain(|m| m.name != mod_name);
-    save_config(app, new_config)?;
+    write_config(&app, &new_config)?;
 
     Ok(OperationResult::success(format!(
         "MOD \"{}\" 已删除",
         mod_name
     )))
 }
+
+/// 重命名 MOD
+///
+/// 同时更新数据目录中的文件夹、mod-info.json、config.json（包括各方案）以及
+/// 部署清单中的文件归属，任一步失败时撤销已完成的修改。游戏目录中复制或硬链接
+/// 部署的文件保持不变，以符号链接部署的文件会重新指向新的文件夹。
+#[tauri::command]
+pub fn rename_mod(
+    app: AppHandle,
+    mod_name: String,
+    new_name: String,
+) -> Result<OperationResult, String> {
+    use crate::commands::config::load_mod_info;
+
+    // 1. 验证新名称（旧名称只需能安全地定位到文件夹）
+    let new_name = new_name.trim().to_string();
+    validate_existing_mod_name(&mod_name)?;
+    validate_mod_name(&new_name)?;
+    if new_name == mod_name {
+        return Ok(OperationResult::success("MOD 名称未改变"));
+    }
+
+    let _data = try_lock_data(&app)?;
+    let config = load_config(app.clone())?;
+    if !config.mods.iter().any(|m| m.name == mod_name) {
+        return Err(format!("MOD \"{}\" 不存在", mod_name));
+    }
+    // 文件夹名在 Windows 下不区分大小写，仅大小写不同的名称视为同一个 MOD
+    let case_only = mod_name.to_lowercase() == new_name.to_lowercase();
+    if !case_only && is_mod_name_taken(&config, &new_name) {
+        return Err(format!("MOD \"{}\" 已存在", new_name));
+    }
+
+    let data_dir = get_data_dir(&app)?;
+    let old_dir = data_dir.join(&mod_name);
+    let new_dir = data_dir.join(&new_name);
+    if !case_only && new_dir.exists() {
+        return Err(format!("MOD 目录已存在: {:?}", new_dir));
+    }
+
+    let mut mod_info = load_mod_info(app.clone(), mod_name.clone())?;
+
+    // 2. 重命名文件夹（仅大小写不同时经过临时名称，兼容不区分大小写的文件系统）
+    let rename_dir = |from: &Path, to: &Path| -> Result<(), String> {
+        if case_only {
+            let temp_dir = get_staging_dir(&app)?.join(format!("{}.rename", new_name));
+            fs::create_dir_all(temp_dir.parent().unwrap_or(&data_dir))
+                .map_err(|e| format!("创建暂存目录失败: {}", e))?;
+            fs::rename(from, &temp_dir)
+                .and_then(|_| fs::rename(&temp_dir, to))
+                .map_err(|e| format!("重命名 MOD 目录失败: {}", e))
+        } else {
+            fs::rename(from, to).map_err(|e| format!("重命名 MOD 目录失败: {}", e))
+        }
+    };
+    rename_dir(&old_dir, &new_dir)?;
+
+    // 3. 更新 mod-info.json、config.json 和部署清单
+    let mut new_config = config.clone();
+    let profile_mods = new_config.profiles.iter_mut().flat_map(|p| p.mods.iter_mut());
+    for mod_item in new_config.mods.iter_mut().chain(profile_mods) {
+        if mod_item.name == mod_name {
+            mod_item.name = new_name.clone();
+        }
+    }
+    mod_info.name = new_name.clone();
+
+    let manifest = load_manifest(&app)?;
+    let result = write_json_file(&new_dir.join("mod-info.json"), &mod_info, " MOD 信息")
+        .and_then(|_| write_config(&app, &new_config))
+        .and_then(|_| match &manifest {
+            Some(manifest) => {
+                let mut new_manifest = manifest.clone();
+                for deployed in new_manifest.files.values_mut() {
+                    if deployed.owner == mod_name {
+                        deployed.owner = new_name.clone();
+                    }
+                }
+                save_manifest(&app, &new_manifest)
+            }
+            None => Ok(()),
+        });
+
+    // 4. 失败时恢复原来的名称
+    if let Err(e) = result {
+        let _ = write_config(&app, &config);
+        if let Some(manifest) = &manifest {
+            let _ = save_manifest(&app, manifest);
+        }
+        if rename_dir(&new_dir, &old_dir).is_ok() {
+            mod_info.name = mod_name.clone();
+            let _ = write_json_file(&old_dir.join("mod-info.json"), &mod_info, " MOD 信息");
+        }
+        return Err(e);
+    }
+
+    // 5. 以符号链接部署的文件仍指向旧目录，重新部署失效的文件
+    let mut message = format!("MOD \"{}\" 已重命名为 \"{}\"", mod_name, new_name);
+    if let Err(e) =
+        sync_deployment(&app, &data_dir, &new_config, &new_config, false, &Job::detached())
+    {
+        message = format!("{}，但重新部署失效的文件失败: {}", message, e);
+    }
+
+    Ok(OperationResult::success(message))
+}
+
+/// 调整 MOD 排序
+///
+/// `mod_names` 为所有已安装 MOD 的新顺序（排在前面的优先），只重新部署胜出
+/// MOD 发生变化的文件。进度通过 `file-progress` 事件发送，被取消时恢复原来
+/// 的部署。
+#[tauri::command]
+pub async fn reorder_mods(
+    app: AppHandle,
+    mod_names: Vec<String>,
+    job_id: Option<String>,
+) -> Result<OperationResult, String> {
+    run_job(app, job_id, move |app, job| {
+        let config = load_config(app.clone())?;
+
+        // 1. 验证新顺序恰好包含所有已安装的 MOD
+        let mut seen = HashSet::new();
+        for name in &mod_names {
+            if !seen.insert(name) {
+                return Err(format!("MOD \"{}\" 在排序中重复出现", name));
+            }
+            if !config.mods.iter().any(|m| &m.name == name) {
+                return Err(format!("MOD \"{}\" 不存在", name));
+            }
+        }
+        if let Some(missing) = config.mods.iter().find(|m| !seen.contains(&m.name)) {
+            return Err(format!("排序中缺少 MOD \"{}\"", missing.name));
+        }
+
+        // 2. 按新顺序重新编号
+        let mut new_config = config.clone();
+        for mod_item in &mut new_config.mods {
+            if let Some(index) = mod_names.iter().position(|n| n == &mod_item.name) {
+                mod_item.order = index + 1;
+            }
+        }
+        new_config.mods.sort_by_key(|m| m.order);
+
+        // 3. 同步部署并保存
+        apply_config_change(app, &config, new_config, job)
+    })
+    .await?;
+
+    Ok(OperationResult::success("MOD 排序已更新"))
+}
+
+/// 批量启用、禁用或删除 MOD
+///
+/// 先计算所有修改完成后的最终状态，再一次性同步游戏目录（只有一条进度），
+/// 返回每个 MOD 的结果。无效的项（如 MOD 不存在、同一 MOD 重复出现）不影响
+/// 其他项；同步失败或被取消时整个批次不生效。
+#[tauri::command]
+pub async fn batch_update_mods(
+    app: AppHandle,
+    changes: Vec<ModChange>,
+    job_id: Option<String>,
+) -> Result<Vec<ModChangeResult>, String> {
+    run_job(app, job_id, move |app, job| {
+        let config = load_config(app.clone())?;
+        let data_dir = get_data_dir(app)?;
+
+        // 1. 计算最终状态
+        let mut new_config = config.clone();
+        let mut results = Vec::new();
+        let mut accepted = Vec::new();
+        let mut seen = HashSet::new();
+        for change in changes {
+            let error = if !seen.insert(change.name.clone()) {
+                Some("同一个 MOD 在批量操作中重复出现".to_string())
+            } else if !config.mods.iter().any(|m| m.name == change.name) {
+                Some(format!("MOD \"{}\" 不存在", change.name))
+            } else {
+                None
+            };
+
+            if error.is_none() {
+                match change.action {
+                    ModAction::Enable | ModAction::Disable => {
+                        let enabled = change.action == ModAction::Enable;
+                        for mod_item in &mut new_config.mods {
+                            if mod_item.name == change.name {
+                                mod_item.enabled = enabled;
+                            }
+                        }
+                    }
+                    ModAction::Delete => remove_mod_from_config(&mut new_config, &change.name),
+                }
+                accepted.push(results.len());
+            }
+
+            results.push(ModChangeResult {
+                name: change.name,
+                action: change.action,
+                result: match error {
+                    Some(error) => OperationResult::error(error),
+                    None => OperationResult::success(""),
+                },
+            });
+        }
+
+        // 2. 一次性同步游戏目录并保存配置
+        apply_config_change(app, &config, new_config, job)?;
+
+        // 3. 删除被移除 MOD 的目录
+        for index in accepted {
+            let result = &mut results[index];
+            let message = match result.action {
+                ModAction::Enable => format!("MOD \"{}\" 已启用", result.name),
+                ModAction::Disable => format!("MOD \"{}\" 已禁用", result.name),
+                ModAction::Delete => {
+                    let mod_dir = data_dir.join(&result.name);
+                    if let Err(e) = fs::remove_dir_all(&mod_dir) {
+                        result.result = OperationResult::error(format!(
+                            "MOD \"{}\" 已从配置中移除，但删除目录失败: {}",
+                            result.name, e
+                        ));
+                        continue;
+                    }
+                    format!("MOD \"{}\" 已删除", result.name)
+                }
+            };
+            result.result = OperationResult::success(message);
+        }
+
+        Ok(results)
+    })
+    .await
+}
@@ -0,0 +1,180 @@
+use crate::commands::config::load_config;
+use crate::models::AppConfig;
+use std::path::{Component, Path};
+use tauri::AppHandle;
+
+/// MOD 名称的最大长度（字符数），为游戏内的深层路径留出余量
+const MAX_NAME_LEN: usize = 128;
+
+/// Windows 下不能出现在文件名中的字符
+const INVALID_CHARS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Windows 保留的设备名（不区分大小写，带扩展名同样无效，如 `CON.txt`）
+const RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// 数据目录中程序自己的文件，写入时还会产生 `.tmp`、`.bak` 等同名前缀的文件
+const INTERNAL_FILES: &[&str] = &["config.json", "deployment.json"];
+
+/// 未能从文件名得到有效名称时使用的默认名称
+const FALLBACK_NAME: &str = "未命名 MOD";
+
+/// 支持的压缩包扩展名，较长的复合扩展名在前
+const ARCHIVE_EXTENSIONS: &[&str] = &[
+    ".tar.gz", ".tar.xz", ".tgz", ".txz", ".tar", ".zip", ".7z", ".rar",
+];
+
+fn is_invalid_char(c: char) -> bool {
+    INVALID_CHARS.contains(&c) || c.is_control()
+}
+
+fn is_internal_file_name(name: &str) -> bool {
+    let name = name.to_lowercase();
+    INTERNAL_FILES.iter().any(|file| {
+        name == *file
+            || name
+                .strip_prefix(file)
+                .is_some_and(|rest| rest.starts_with('.'))
+    })
+}
+
+fn is_reserved_name(name: &str) -> bool {
+    let stem = name.split('.').next().unwrap_or(name).trim_end();
+    RESERVED_NAMES
+        .iter()
+        .any(|reserved| stem.eq_ignore_ascii_case(reserved))
+}
+
+/// 检查 MOD 名称能否在 Windows 和 Linux 下作为数据目录中的文件夹名
+///
+/// 名称会直接拼接到数据目录后，因此同时拒绝路径分隔符和 `..` 等越出数据目录
+/// 的写法。
+pub fn validate_mod_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("MOD 名称不能为空".to_string());
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+        return Err(format!("MOD 名称不能超过 {} 个字符", MAX_NAME_LEN));
+    }
+    if let Some(c) = name.chars().find(|c| is_invalid_char(*c)) {
+        return Err(format!("MOD 名称不能包含字符 {:?}", c));
+    }
+    // 数据目录中以 `.` 开头的文件夹（如 .backup、.staging）由程序内部使用，
+    // 同时排除了 `.` 和 `..`
+    if name.starts_with('.') {
+        return Err("MOD 名称不能以 . 开头".to_string());
+    }
+    if name.starts_with(' ') || name.ends_with(' ') || name.ends_with('.') {
+        return Err("MOD 名称不能以空格开头，也不能以空格或 . 结尾".to_string());
+    }
+    if is_reserved_name(name) {
+        return Err(format!("MOD 名称不能使用系统保留名称: {}", name));
+    }
+    if is_internal_file_name(name) {
+        return Err(format!("MOD 名称与数据目录中的配置文件冲突: {}", name));
+    }
+    Ok(())
+}
+
+/// 检查已安装 MOD 的名称能否安全地拼接到数据目录后
+///
+/// 只拒绝路径分隔符、`..` 等越出数据目录的写法以及程序内部使用的 `.` 开头的
+/// 名称。旧版本安装的 MOD 可能不满足 [`validate_mod_name`]（如 `Foo: Bar`），
+/// 仍需要能够加载、删除和重命名。
+pub fn validate_existing_mod_name(name: &str) -> Result<(), String> {
+    let mut components = Path::new(name).components();
+    let is_single_folder =
+        matches!(components.next(), Some(Component::Normal(_))) && components.next().is_none();
+    if !is_single_folder || name.contains(['/', '\\']) || name.starts_with('.') {
+        return Err(format!("无效的 MOD 名称: {}", name));
+    }
+    Ok(())
+}
+
+/// 是否已有同名 MOD（不区分大小写，文件夹名在 Windows 下不区分大小写）
+pub fn is_mod_name_taken(config: &AppConfig, name: &str) -> bool {
+    let name = name.to_lowercase();
+    config.mods.iter().any(|m| m.name.to_lowercase() == name)
+}
+
+/// 将任意文本转换为有效的 MOD 名称（满足 [`validate_mod_name`]）
+pub fn sanitize_mod_name(name: &str) -> String {
+    let replaced: String = name
+        .chars()
+        .map(|c| if is_invalid_char(c) { '_' } else { c })
+        .collect();
+
+    let mut sanitized: String = replaced
+        .trim_start_matches(|c: char| c == '.' || c.is_whitespace())
+        .chars()
+        .take(MAX_NAME_LEN)
+        .collect();
+    let len = sanitized
+        .trim_end_matches(|c: char| c == '.' || c.is_whitespace())
+        .len();
+    sanitized.truncate(len);
+
+    if sanitized.is_empty() {
+        return FALLBACK_NAME.to_string();
+    }
+    if is_reserved_name(&sanitized) || is_internal_file_name(&sanitized) {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+/// 去掉 Nexus Mods 下载文件名末尾的 `-<MOD ID>-<版本>-<时间戳>`
+fn strip_nexus_suffix(stem: &str) -> &str {
+    let parts: Vec<&str> = stem.split('-').collect();
+    let numeric = parts
+        .iter()
+        .rev()
+        .take_while(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+        .count();
+
+    // 至少包含 MOD ID 和 10 位的时间戳
+    let is_timestamp = parts.last().is_some_and(|last| last.len() >= 10);
+    if numeric >= 2 && numeric < parts.len() && is_timestamp {
+        let kept = parts.len() - numeric;
+        let len = parts[..kept].iter().map(|p| p.len() + 1).sum::<usize>() - 1;
+        &stem[..len]
+    } else {
+        stem
+    }
+}
+
+/// 根据压缩包文件名生成可用的 MOD 名称
+///
+/// 去掉压缩包扩展名和 Nexus 下载文件名的后缀，替换无效字符；与已安装的
+/// MOD 重名（不区分大小写）时追加序号。
+#[tauri::command]
+pub fn suggest_mod_name(app: AppHandle, archive_path: String) -> Result<String, String> {
+    let file_name = Path::new(&archive_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let lower = file_name.to_lowercase();
+    let stem = ARCHIVE_EXTENSIONS
+        .iter()
+        .find(|ext| lower.ends_with(*ext))
+        .map_or(file_name.as_str(), |ext| {
+            &file_name[..file_name.len() - ext.len()]
+        });
+
+    let base = sanitize_mod_name(strip_nexus_suffix(stem));
+
+    let config = load_config(app)?;
+    let taken = |name: &str| is_mod_name_taken(&config, name);
+
+    let mut name = base.clone();
+    let mut index = 2;
+    while taken(&name) {
+        name = format!("{} ({})", base, index);
+        index += 1;
+    }
+
+    Ok(name)
+}
@@ -0,0 +1,183 @@
+use crate::commands::config::{load_config, write_config};
+use crate::commands::jobs::{run_job, try_lock_data};
+use crate::commands::mod_manager::apply_config_change;
+use crate::models::{AppConfig, ModConfigItem, OperationResult, Profile};
+use tauri::AppHandle;
+
+/// 确保当前方案在 `profiles` 中有对应条目（旧版本的配置没有方案）
+fn ensure_active_profile(config: &mut AppConfig) {
+    if !config
+        .profiles
+        .iter()
+        .any(|p| p.name == config.active_profile)
+    {
+        config.profiles.insert(
+            0,
+            Profile {
+                name: config.active_profile.clone(),
+                mods: Vec::new(),
+            },
+        );
+    }
+}
+
+/// 验证新方案名称，返回去掉首尾空白后的名称
+fn validate_profile_name(config: &AppConfig, name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("方案名称不能为空".to_string());
+    }
+    if config.profiles.iter().any(|p| p.name == name) || config.active_profile == name {
+        return Err(format!("方案 \"{}\" 已存在", name));
+    }
+    Ok(name.to_string())
+}
+
+/// 获取方案的 MOD 列表，当前方案以 `config.mods` 为准
+fn profile_mods(config: &AppConfig, name: &str) -> Result<Vec<ModConfigItem>, String> {
+    if name == config.active_profile {
+        return Ok(config.mods.clone());
+    }
+    config
+        .profiles
+        .iter()
+        .find(|p| p.name == name)
+        .map(|p| p.mods.clone())
+        .ok_or_else(|| format!("方案 \"{}\" 不存在", name))
+}
+
+/// 使方案的 MOD 列表与已安装的 MOD 一致
+///
+/// 去掉已删除的 MOD，方案创建后才安装的 MOD 以禁用状态追加到末尾，并将
+/// `order` 重新编号为 1..n。
+fn reconcile_profile_mods(config: &AppConfig, mut mods: Vec<ModConfigItem>) -> Vec<ModConfigItem> {
+    mods.retain(|m| config.mods.iter().any(|installed| installed.name == m.name));
+    mods.sort_by_key(|m| m.order);
+
+    let mut installed: Vec<&ModConfigItem> = config.mods.iter().collect();
+    installed.sort_by_key(|m| m.order);
+    for mod_item in installed {
+        if !mods.iter().any(|m| m.name == mod_item.name) {
+            mods.push(ModConfigItem {
+                name: mod_item.name.clone(),
+                order: 0,
+                enabled: false,
+            });
+        }
+    }
+
+    for (index, mod_item) in mods.iter_mut().enumerate() {
+        mod_item.order = index + 1;
+    }
+    mods
+}
+
+/// 创建方案，包含所有已安装的 MOD（按当前排序，全部禁用）
+#[tauri::command]
+pub fn create_profile(app: AppHandle, profile_name: String) -> Result<OperationResult, String> {
+    let _data = try_lock_data(&app)?;
+    let mut config = load_config(app.clone())?;
+    let name = validate_profile_name(&config, &profile_name)?;
+
+    let mods = reconcile_profile_mods(&config, Vec::new());
+    ensure_active_profile(&mut config);
+    config.profiles.push(Profile {
+        name: name.clone(),
+        mods,
+    });
+    write_config(&app, &config)?;
+
+    Ok(OperationResult::success(format!(
+        "方案 \"{}\" 已创建",
+        name
+    )))
+}
+
+/// 复制已有方案的排序和启用状态
+#[tauri::command]
+pub fn clone_profile(
+    app: AppHandle,
+    source_name: String,
+    profile_name: String,
+) -> Result<OperationResult, String> {
+    let _data = try_lock_data(&app)?;
+    let mut config = load_config(app.clone())?;
+    let name = validate_profile_name(&config, &profile_name)?;
+
+    let mods = reconcile_profile_mods(&config, profile_mods(&config, &source_name)?);
+    ensure_active_profile(&mut config);
+    config.profiles.push(Profile {
+        name: name.clone(),
+        mods,
+    });
+    write_config(&app, &config)?;
+
+    Ok(OperationResult::success(format!(
+        "已从方案 \"{}\" 复制方案 \"{}\"",
+        source_name, name
+    )))
+}
+
+/// 删除方案（不能删除当前方案）
+#[tauri::command]
+pub fn delete_profile(app: AppHandle, profile_name: String) -> Result<OperationResult, String> {
+    let _data = try_lock_data(&app)?;
+    let mut config = load_config(app.clone())?;
+
+    if profile_name == config.active_profile {
+        return Err("不能删除当前使用的方案，请先切换到其他方案".to_string());
+    }
+    let count = config.profiles.len();
+    config.profiles.retain(|p| p.name != profile_name);
+    if config.profiles.len() == count {
+        return Err(format!("方案 \"{}\" 不存在", profile_name));
+    }
+    write_config(&app, &config)?;
+
+    Ok(OperationResult::success(format!(
+        "方案 \"{}\" 已删除",
+        profile_name
+    )))
+}
+
+/// 切换方案
+///
+/// 只重新部署与当前部署不同的文件；进度通过 `file-progress` 事件发送，
+/// 被取消时恢复到切换前的状态。
+#[tauri::command]
+pub async fn switch_profile(
+    app: AppHandle,
+    profile_name: String,
+    job_id: Option<String>,
+) -> Result<OperationResult, String> {
+    let name = profile_name.clone();
+    run_job(app, job_id, move |app, job| {
+        let config = load_config(app.clone())?;
+        if name == config.active_profile {
+            return Ok(());
+        }
+
+        let mods = reconcile_profile_mods(&config, profile_mods(&config, &name)?);
+
+        // 保存当前方案，再换成目标方案
+        let mut new_config = config.clone();
+        ensure_active_profile(&mut new_config);
+        for profile in &mut new_config.profiles {
+            if profile.name == config.active_profile {
+                profile.mods = config.mods.clone();
+            } else if profile.name == name {
+                profile.mods = Vec::new();
+            }
+        }
+        new_config.mods = mods;
+        new_config.active_profile = name;
+
+        apply_config_change(app, &config, new_config, job)
+    })
+    .await?;
+
+    Ok(OperationResult::success(format!(
+        "已切换到方案 \"{}\"",
+        profile_name
+    )))
+}
@@ -0,0 +1,213 @@
+use crate::commands::config::{get_data_dir, get_staging_dir, load_config};
+use crate::commands::file_ops::{
+    case_insensitive_key, find_entry_case_insensitive, list_files_recursive, move_file,
+};
+use crate::commands::jobs::{run_job, Job};
+use crate::commands::mod_manager::{
+    apply_config_change, commit_staged_mod, load_or_seed_manifest, relative_key,
+};
+use crate::commands::naming::{is_mod_name_taken, validate_mod_name};
+use crate::models::{AppConfig, ModFiles, ModInfo, OperationResult, UntrackedFile};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+/// 游戏根目录中的原版文件（小写），扫描时忽略
+const VANILLA_ROOT_FILES: &[&str] = &[
+    "monsterhunterworld.exe",
+    "amd_ags_x64.dll",
+    "steam_api64.dll",
+    "steam_appid.txt",
+    "installscript.vdf",
+    "crashreport.exe",
+    "crashreport.dll",
+    "graphics_option.ini",
+    "graphics_option_presets.ini",
+    "nvngx_dlss.dll",
+];
+
+/// 列出游戏目录中未被管理的文件（游戏目录中的绝对路径, 相对路径）
+///
+/// 扫描 nativepc 中的所有文件和游戏根目录下的文件（不含子目录），排除部署
+/// 清单中记录的文件和已知的原版文件。
+fn find_untracked_files(
+    app: &AppHandle,
+    config: &AppConfig,
+) -> Result<Vec<(PathBuf, String)>, String> {
+    let game_dir = Path::new(&config.game_directory);
+    if !game_dir.exists() {
+        return Err("游戏目录不存在".to_string());
+    }
+
+    let data_dir = get_data_dir(app)?;
+    let manifest = load_or_seed_manifest(app, &data_dir, config)?;
+
+    let mut files = Vec::new();
+    if let Some(nativepc) = find_entry_case_insensitive(game_dir, "nativepc") {
+        files.extend(
+            list_files_recursive(&nativepc).map_err(|e| format!("读取 nativepc 失败: {}", e))?,
+        );
+    }
+    for entry in fs::read_dir(game_dir).map_err(|e| format!("读取游戏目录失败: {}", e))? {
+        let path = entry.map_err(|e| format!("读取目录项失败: {}", e))?.path();
+        if path.is_file() {
+            files.push(path);
+        }
+    }
+
+    let mut untracked: Vec<(PathBuf, String)> = files
+        .into_iter()
+        .filter_map(|path| {
+            let relative = relative_key(path.strip_prefix(game_dir).ok()?);
+            let key = case_insensitive_key(&relative);
+            let is_vanilla = VANILLA_ROOT_FILES.contains(&key.as_str());
+            (!is_vanilla && !manifest.files.contains_key(&key)).then_some((path, relative))
+        })
+        .collect();
+    untracked.sort_by(|a, b| a.1.cmp(&b.1));
+
+    Ok(untracked)
+}
+
+/// 扫描游戏目录中未被管理的文件（如手动安装的 MOD）
+#[tauri::command]
+pub fn scan_untracked_files(app: AppHandle) -> Result<Vec<UntrackedFile>, String> {
+    let config = load_config(app.clone())?;
+
+    Ok(find_untracked_files(&app, &config)?
+        .into_iter()
+        .map(|(path, relative)| UntrackedFile {
+            size: fs::metadata(&path).map_or(0, |m| m.len()),
+            path: relative,
+        })
+        .collect())
+}
+
+/// 将选中的未管理文件收编为新的 MOD
+///
+/// 文件从游戏目录移动到数据目录中的新 MOD（生成 mod-info.json），随后启用
+/// 该 MOD 重新部署，游戏中的内容保持不变。`paths` 为 `scan_untracked_files`
+/// 返回的相对路径。
+#[tauri::command]
+pub async fn adopt_untracked_files(
+    app: AppHandle,
+    mod_name: String,
+    paths: Vec<String>,
+    categories: Vec<String>,
+    job_id: Option<String>,
+) -> Result<OperationResult, String> {
+    let name = mod_name.clone();
+    let count = run_job(app, job_id, move |app, job| {
+        adopt_untracked_files_with_job(app, name, paths, categories, job)
+    })
+    .await?;
+
+    Ok(OperationResult::success(format!(
+        "已将 {} 个文件收编为 MOD \"{}\"",
+        count, mod_name
+    )))
+}
+
+fn adopt_untracked_files_with_job(
+    app: &AppHandle,
+    mod_name: String,
+    paths: Vec<String>,
+    categories: Vec<String>,
+    job: &Job,
+) -> Result<usize, String> {
+    // 1. 验证 MOD 名称
+    validate_mod_name(&mod_name)?;
+    let config = load_config(app.clone())?;
+    if is_mod_name_taken(&config, &mod_name) {
+        return Err(format!("MOD \"{}\" 已存在", mod_name));
+    }
+
+    let mod_dir = get_data_dir(app)?.join(&mod_name);
+    if mod_dir.exists() {
+        return Err(format!("MOD 目录已存在: {:?}", mod_dir));
+    }
+
+    // 2. 只接受当前确实未被管理的文件
+    if paths.is_empty() {
+        return Err("没有选择要收编的文件".to_string());
+    }
+    let requested: HashSet<String> = paths.iter().map(|p| case_insensitive_key(p)).collect();
+    let selected: Vec<(PathBuf, String)> = find_untracked_files(app, &config)?
+        .into_iter()
+        .filter(|(_, relative)| requested.contains(&case_insensitive_key(relative)))
+        .collect();
+    if selected.len() != requested.len() {
+        return Err("部分文件不存在或已被管理，请重新扫描".to_string());
+    }
+
+    // 3. 将文件移动到暂存目录，nativepc 文件夹统一为小写
+    let staging_dir = get_staging_dir(app)?.join(&mod_name);
+    if staging_dir.exists() {
+        fs::remove_dir_all(&staging_dir).map_err(|e| format!("清理暂存目录失败: {}", e))?;
+    }
+
+    let mut moved = Vec::new();
+    let result = move_into_staging(&selected, &staging_dir, &mut moved, job).and_then(|_| {
+        let mod_info = ModInfo {
+            name: mod_name.clone(),
+            nexus_id: None,
+            categories,
+            enabled: false,
+            install_date: chrono::Utc::now().to_rfc3339(),
+            file_size: 0,
+            files: ModFiles::default(),
+        };
+        commit_staged_mod(app, &staging_dir, &mod_dir, config.clone(), mod_info, job)
+    });
+
+    // 失败时把文件放回游戏目录
+    if let Err(e) = result {
+        for (original, staged) in moved.iter().rev() {
+            let _ = move_file(staged, original);
+        }
+        let _ = fs::remove_dir_all(&staging_dir);
+        return Err(e);
+    }
+
+    // 4. 启用新 MOD，将文件部署回游戏目录
+    let current = load_config(app.clone())?;
+    let mut new_config = current.clone();
+    for mod_item in &mut new_config.mods {
+        if mod_item.name == mod_name {
+            mod_item.enabled = true;
+        }
+    }
+    apply_config_change(app, &current, new_config, &Job::detached())
+        .map_err(|e| format!("MOD \"{}\" 已创建，但启用失败: {}", mod_name, e))?;
+
+    Ok(selected.len())
+}
+
+/// 将文件移动到暂存目录，`moved` 记录已移动的文件（原路径, 暂存路径）以便回滚
+fn move_into_staging(
+    files: &[(PathBuf, String)],
+    staging_dir: &Path,
+    moved: &mut Vec<(PathBuf, PathBuf)>,
+    job: &Job,
+) -> Result<(), String> {
+    fs::create_dir_all(staging_dir).map_err(|e| format!("创建暂存目录失败: {}", e))?;
+
+    job.begin(files.len(), 0);
+    for (path, relative) in files {
+        job.check_cancelled()?;
+
+        let target = match relative.split_once('/') {
+            Some((first, rest)) if first.eq_ignore_ascii_case("nativepc") => {
+                staging_dir.join("nativepc").join(rest)
+            }
+            _ => staging_dir.join(relative),
+        };
+        move_file(path, &target).map_err(|e| format!("移动文件 {} 失败: {}", relative, e))?;
+        moved.push((path.clone(), target));
+
+        job.advance(relative, 0);
+    }
+
+    Ok(())
+}
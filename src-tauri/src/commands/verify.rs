@@ -0,0 +1,172 @@
+use crate::commands::config::{get_data_dir, load_config, save_manifest};
+use crate::commands::file_ops::{hash_file, resolve_case_insensitive};
+use crate::commands::jobs::{run_job, Job};
+use crate::commands::mod_manager::{
+    build_deploy_plan, deploy_file, load_or_seed_manifest, undeploy_file, DeployContext, DeployPlan,
+};
+use crate::models::{
+    DeployedFile, DeploymentManifest, DriftKind, DriftedFile, OperationResult, VerifyReport,
+};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use tauri::AppHandle;
+
+/// 比较游戏目录中的文件与部署清单中记录的大小和哈希
+///
+/// 清单中尚未记录哈希时（部署时不计算哈希），与胜出 MOD 的源文件比较，一致时
+/// 将哈希补记到清单中，之后的校验只需读取游戏目录中的文件。
+fn matches_recorded(full_path: &Path, deployed: &mut DeployedFile, source: &Path) -> bool {
+    if !fs::metadata(full_path).is_ok_and(|m| m.len() == deployed.size) {
+        return false;
+    }
+    let Ok(hash) = hash_file(full_path) else {
+        return false;
+    };
+
+    if !deployed.hash.is_empty() {
+        return hash == deployed.hash;
+    }
+    if hash_file(source).is_ok_and(|source_hash| source_hash == hash) {
+        deployed.hash = hash;
+        return true;
+    }
+    false
+}
+
+/// 找出与部署计划不一致的文件，返回（检查的文件数, 键 -> 偏差）
+fn find_drift(
+    ctx: &DeployContext,
+    manifest: &mut DeploymentManifest,
+    plan: &DeployPlan,
+    job: &Job,
+) -> Result<(usize, Vec<(String, DriftedFile)>), String> {
+    let keys: BTreeSet<String> = manifest.files.keys().chain(plan.keys()).cloned().collect();
+    let mut drifted = Vec::new();
+
+    job.begin(keys.len(), 0);
+    for key in &keys {
+        job.check_cancelled()?;
+
+        let deployed = manifest.files.get_mut(key);
+        let planned = plan.get(key);
+        let drift = match (deployed, planned) {
+            (Some(deployed), None) => Some(DriftedFile {
+                path: deployed.path.clone(),
+                owner: deployed.owner.clone(),
+                kind: DriftKind::Unexpected,
+            }),
+            (deployed, Some(planned)) => {
+                let path = deployed
+                    .as_ref()
+                    .map_or(&planned.target, |d| &d.path)
+                    .clone();
+                let full_path = match &deployed {
+                    Some(d) => ctx.game_dir.join(&d.path),
+                    None => resolve_case_insensitive(&ctx.game_dir, Path::new(&planned.target)),
+                };
+
+                // 胜出的 MOD 改变时无需读取文件
+                let kind = if !full_path.exists() {
+                    Some(DriftKind::Missing)
+                } else if !deployed.is_some_and(|d| {
+                    d.owner == planned.owner && matches_recorded(&full_path, d, &planned.source)
+                }) {
+                    Some(DriftKind::Modified)
+                } else {
+                    None
+                };
+
+                kind.map(|kind| DriftedFile {
+                    path,
+                    owner: planned.owner.clone(),
+                    kind,
+                })
+            }
+            (None, None) => None,
+        };
+
+        if let Some(drift) = drift {
+            drifted.push((key.clone(), drift));
+        }
+        job.advance(key, 0);
+    }
+
+    Ok((keys.len(), drifted))
+}
+
+/// 校验部署状态
+///
+/// 按当前配置计算应有的部署，将游戏目录中的文件与部署清单中记录的大小和
+/// 哈希逐个比较，报告缺失、被修改和不应存在的文件。进度通过 `file-progress` 事件
+/// 发送。
+#[tauri::command]
+pub async fn verify_deployment(
+    app: AppHandle,
+    job_id: Option<String>,
+) -> Result<VerifyReport, String> {
+    run_job(app, job_id, |app, job| {
+        let config = load_config(app.clone())?;
+        let data_dir = get_data_dir(app)?;
+
+        let mut manifest = load_or_seed_manifest(app, &data_dir, &config)?;
+        let plan = build_deploy_plan(&data_dir, &config, false)?;
+        let ctx = DeployContext::new(app, &config)?;
+
+        let (checked, drifted) = find_drift(&ctx, &mut manifest, &plan, job)?;
+        // 保存校验过程中补记的哈希
+        save_manifest(app, &manifest)?;
+        Ok(VerifyReport {
+            checked,
+            drifted: drifted.into_iter().map(|(_, drift)| drift).collect(),
+        })
+    })
+    .await
+}
+
+/// 修复部署：只重新部署（或移除）与部署计划不一致的文件
+#[tauri::command]
+pub async fn repair_deployment(
+    app: AppHandle,
+    job_id: Option<String>,
+) -> Result<OperationResult, String> {
+    let repaired = run_job(app, job_id, |app, job| {
+        let config = load_config(app.clone())?;
+        let data_dir = get_data_dir(app)?;
+
+        let mut manifest = load_or_seed_manifest(app, &data_dir, &config)?;
+        let plan = build_deploy_plan(&data_dir, &config, false)?;
+        let ctx = DeployContext::new(app, &config)?;
+        if !ctx.game_dir.exists() {
+            return Err("游戏目录不存在".to_string());
+        }
+
+        // 1. 找出偏差
+        let (_, drifted) = find_drift(&ctx, &mut manifest, &plan, job)?;
+
+        // 2. 逐个修复，无论成功与否都保存清单
+        let mut repair = || -> Result<(), String> {
+            job.begin(drifted.len(), 0);
+            for (key, drift) in &drifted {
+                job.check_cancelled()?;
+                match plan.get(key) {
+                    Some(planned) => deploy_file(&ctx, key, planned, &mut manifest)?,
+                    None => undeploy_file(&ctx, key, &mut manifest)?,
+                }
+                job.advance(&drift.path, 0);
+            }
+            Ok(())
+        };
+        let result = repair();
+        save_manifest(app, &manifest)?;
+        result?;
+
+        Ok(drifted.len())
+    })
+    .await?;
+
+    Ok(OperationResult::success(format!(
+        "已修复 {} 个文件",
+        repaired
+    )))
+}
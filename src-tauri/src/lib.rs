@@ -1,4 +1,5 @@
 mod models;
+mod archive;
 mod commands;
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
@@ -12,23 +13,39 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
+        .manage(commands::JobRegistry::default())
         .invoke_handler(tauri::generate_handler![
             greet,
             commands::load_config,
             commands::save_config,
+            commands::relocate_data_directory,
             commands::load_mod_info,
             commands::save_mod_info,
             commands::load_all_mods,
-            commands::preview_zip_archive,
-            commands::extract_zip_archive,
-            commands::copy_mod_to_game,
-            commands::remove_mod_from_game,
+            commands::preview_archive,
+            commands::suggest_mod_name,
+            commands::extract_archive,
             commands::delete_directory,
             commands::get_directory_size,
             commands::install_mod,
             commands::enable_mod,
             commands::disable_mod,
+            commands::redeploy_all,
+            commands::purge_deployment,
+            commands::verify_deployment,
+            commands::repair_deployment,
+            commands::scan_untracked_files,
+            commands::adopt_untracked_files,
+            commands::detect_conflicts,
+            commands::cancel_job,
             commands::delete_mod,
+            commands::rename_mod,
+            commands::reorder_mods,
+            commands::batch_update_mods,
+            commands::create_profile,
+            commands::clone_profile,
+            commands::delete_profile,
+            commands::switch_profile,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
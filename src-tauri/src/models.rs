@@ -1,4 +1,8 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// 当前的 config.json 结构版本，升级逻辑见 `commands::config` 中的迁移
+pub const CONFIG_SCHEMA_VERSION: u32 = 2;
 
 /// 分类定义
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -8,7 +12,8 @@ pub struct Category {
 }
 
 /// MOD 文件信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ModFiles {
     pub nativepc: Vec<String>,
     pub root: Vec<String>,
@@ -21,10 +26,15 @@ pub struct ModInfo {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub nexus_id: Option<String>,
+    #[serde(default)]
     pub categories: Vec<String>,
+    #[serde(default)]
     pub enabled: bool,
+    #[serde(default)]
     pub install_date: String,
+    #[serde(default)]
     pub file_size: u64,
+    #[serde(default)]
     pub files: ModFiles,
 }
 
@@ -33,36 +43,89 @@ pub struct ModInfo {
 pub struct ModConfigItem {
     pub name: String,
     pub order: usize,
+    #[serde(default)]
     pub enabled: bool,
 }
 
+/// 默认方案名称
+pub const DEFAULT_PROFILE: &str = "默认";
+
+/// 方案：一套 MOD 排序和启用状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    pub mods: Vec<ModConfigItem>,
+}
+
 /// 全局配置（config.json）
+///
+/// 缺失的字段使用默认值，新增字段时无需迁移。
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct AppConfig {
     pub version: String,
+    /// 结构版本，旧版本的文件没有该字段（视为 1）
+    pub schema_version: u32,
     pub game_directory: String,
+    /// 数据目录位置（只读）：以 data-location.json 为准，加载时从中读取，
+    /// 只能通过 `relocate_data_directory` 修改
     pub data_directory: String,
+    /// 当前方案的 MOD 排序和启用状态，包含所有已安装的 MOD
     pub mods: Vec<ModConfigItem>,
+    /// 当前方案名称
+    pub active_profile: String,
+    /// 所有方案；当前方案以 `mods` 为准，其条目中的 `mods` 仅在切换离开时写入
+    pub profiles: Vec<Profile>,
     pub categories: Vec<Category>,
     pub settings: AppSettings,
 }
 
+/// 将 MOD 文件部署到游戏目录的方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DeployMethod {
+    /// 复制文件
+    #[default]
+    Copy,
+    /// 硬链接，不占用额外空间；数据目录与游戏目录需位于同一文件系统
+    Hardlink,
+    /// 符号链接（Windows 下需要开发者模式或管理员权限）
+    Symlink,
+}
+
 /// 应用设置
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct AppSettings {
     pub auto_detect_conflicts: bool,
     pub show_conflict_warnings: bool,
+    /// 部署方式，无法使用时退化为复制
+    pub deploy_method: DeployMethod,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            auto_detect_conflicts: true,
+            show_conflict_warnings: true,
+            deploy_method: DeployMethod::default(),
+        }
+    }
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
             version: "0.1.0".to_string(),
+            schema_version: CONFIG_SCHEMA_VERSION,
             game_directory: String::new(),
             data_directory: "./data".to_string(),
             mods: Vec::new(),
+            active_profile: DEFAULT_PROFILE.to_string(),
+            profiles: vec![Profile {
+                name: DEFAULT_PROFILE.to_string(),
+                mods: Vec::new(),
+            }],
             categories: vec![
                 Category {
                     name: "武器".to_string(),
@@ -77,14 +140,63 @@ impl Default for AppConfig {
                     color: "#3357FF".to_string(),
                 },
             ],
-            settings: AppSettings {
-                auto_detect_conflicts: true,
-                show_conflict_warnings: true,
-            },
+            settings: AppSettings::default(),
         }
     }
 }
 
+/// 数据目录指针（应用数据目录下的 data-location.json）
+///
+/// 数据目录可以被移动到其他位置，config.json 也随之移动，因此需要在固定位置
+/// 记录数据目录所在的路径。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataLocation {
+    /// 数据目录路径，相对路径相对于应用数据目录
+    pub data_directory: String,
+}
+
+/// 已部署到游戏目录的单个文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeployedFile {
+    /// 游戏目录中的实际相对路径（沿用磁盘上已有的大小写）
+    pub path: String,
+    /// 提供该文件的 MOD 名称
+    pub owner: String,
+    /// 文件内容哈希（blake3），部署时不计算，为空表示尚未记录（校验部署时补记）
+    #[serde(default)]
+    pub hash: String,
+    pub size: u64,
+    /// 实际使用的部署方式
+    #[serde(default)]
+    pub method: DeployMethod,
+}
+
+/// 部署清单（deployment.json，与 config.json 位于同一目录）
+///
+/// 键为游戏目录内小写的相对路径（以 `/` 分隔）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeploymentManifest {
+    pub files: BTreeMap<String, DeployedFile>,
+    /// 首次部署前已存在、被备份到数据目录 `.backup` 中的原始文件
+    #[serde(default)]
+    pub backups: BTreeSet<String>,
+}
+
+/// 两个 MOD 之间的文件冲突
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModConflict {
+    /// 排序靠前、文件生效的 MOD
+    pub winner: String,
+    /// 文件被覆盖的 MOD
+    pub loser: String,
+    /// 冲突的文件（游戏目录内的相对路径）
+    pub files: Vec<String>,
+}
+
 /// 压缩包文件树节点
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -92,6 +204,14 @@ pub struct ArchiveFileNode {
     pub name: String,
     pub path: String,
     pub is_directory: bool,
+    /// 解压后的大小（目录为其下所有文件之和）
+    pub size: u64,
+    /// 文件数量（文件为 1，目录为其下所有文件之和）
+    pub file_count: usize,
+    /// 是否为默认安装根目录下的 nativepc 文件夹
+    pub is_native_pc_root: bool,
+    /// 是否会被复制到游戏根目录（默认安装根目录下、nativepc 之外的内容）
+    pub lands_in_game_root: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub children: Option<Vec<ArchiveFileNode>>,
 }
@@ -102,24 +222,53 @@ pub struct ArchiveFileNode {
 pub struct ArchivePreview {
     pub has_native_pc: bool,
     pub native_pc_path: String,
+    /// 默认安装的根目录（剥离外层包装目录后），空字符串表示压缩包根目录
+    pub install_root: String,
+    /// 所有可安装的根目录（压缩包包含多个变体时有多个）
+    pub install_roots: Vec<String>,
+    pub file_count: usize,
+    pub total_size: u64,
+    /// 文件树的顶层节点
     pub files: Vec<ArchiveFileNode>,
 }
 
-/// 文件操作进度
+/// 文件操作进度（通过 `file-progress` 事件发送）
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct FileProgress {
+    /// 所属后台任务，可用于 `cancel_job`
+    pub job_id: String,
     pub current: usize,
     pub total: usize,
+    pub bytes: u64,
+    pub total_bytes: u64,
     pub percentage: f32,
     pub current_file: Option<String>,
 }
 
+impl FileProgress {
+    pub fn new(job_id: &str) -> Self {
+        Self {
+            job_id: job_id.to_string(),
+            current: 0,
+            total: 0,
+            bytes: 0,
+            total_bytes: 0,
+            percentage: 0.0,
+            current_file: None,
+        }
+    }
+}
+
 /// 操作结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OperationResult {
     pub success: bool,
     pub message: Option<String>,
     pub error: Option<String>,
+    /// 被跳过的条目（如压缩包中越出目标目录的路径、仅大小写不同的重名文件）
+    #[serde(default)]
+    pub skipped: Vec<String>,
 }
 
 impl OperationResult {
@@ -128,6 +277,7 @@ impl OperationResult {
             success: true,
             message: Some(message.into()),
             error: None,
+            skipped: Vec::new(),
         }
     }
 
@@ -136,6 +286,78 @@ impl OperationResult {
             success: false,
             message: None,
             error: Some(error.into()),
+            skipped: Vec::new(),
         }
     }
+
+    pub fn with_skipped(mut self, skipped: Vec<String>) -> Self {
+        self.skipped = skipped;
+        self
+    }
+}
+
+/// 批量操作中对单个 MOD 的修改
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ModAction {
+    Enable,
+    Disable,
+    Delete,
+}
+
+/// 批量操作的一项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModChange {
+    pub name: String,
+    pub action: ModAction,
+}
+
+/// 批量操作中单个 MOD 的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModChangeResult {
+    pub name: String,
+    pub action: ModAction,
+    #[serde(flatten)]
+    pub result: OperationResult,
+}
+
+/// 部署偏差的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DriftKind {
+    /// 应当部署的文件不存在
+    Missing,
+    /// 文件存在，但与胜出 MOD 的文件不一致（大小或哈希不同）
+    Modified,
+    /// 清单中记录的文件已不应由任何已启用的 MOD 提供
+    Unexpected,
+}
+
+/// 与部署状态不一致的文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DriftedFile {
+    /// 游戏目录中的相对路径
+    pub path: String,
+    /// 应当提供该文件的 MOD（`Unexpected` 时为清单中记录的 MOD）
+    pub owner: String,
+    pub kind: DriftKind,
+}
+
+/// 部署校验结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyReport {
+    /// 检查的文件数
+    pub checked: usize,
+    pub drifted: Vec<DriftedFile>,
+}
+
+/// 游戏目录中未被管理的文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UntrackedFile {
+    /// 游戏目录中的相对路径（以 `/` 分隔）
+    pub path: String,
+    pub size: u64,
 }
@@ -1,6 +1,7 @@
 import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
 import { ref } from 'vue'
-import type { AppConfig, ModInfo, OperationResult, ArchivePreview } from '@/types/mod'
+import type { AppConfig, ModInfo, ModConflict, OperationResult, ArchivePreview, FileProgress, ModChange, ModChangeResult, VerifyReport, UntrackedFile } from '@/types/mod'
 
 /**
  * MOD 管理器 Composable
@@ -8,8 +9,33 @@ import type { AppConfig, ModInfo, OperationResult, ArchivePreview } from '@/type
 export function useModManager() {
   const config = ref<AppConfig | null>(null)
   const mods = ref<ModInfo[]>([])
+  const conflicts = ref<ModConflict[]>([])
   const loading = ref(false)
   const error = ref<string | null>(null)
+  const progress = ref<FileProgress | null>(null)
+  const currentJobId = ref<string | null>(null)
+
+  /**
+   * 以可取消的后台任务运行命令，并跟踪其进度
+   */
+  async function invokeJob<T>(command: string, args: Record<string, unknown> = {}) {
+    const jobId = `${command}-${Date.now()}`
+    currentJobId.value = jobId
+    progress.value = null
+    const unlisten = await listen<FileProgress>('file-progress', (event) => {
+      if (event.payload.jobId === jobId)
+        progress.value = event.payload
+    })
+
+    try {
+      return await invoke<T>(command, { ...args, jobId })
+    }
+    finally {
+      unlisten()
+      currentJobId.value = null
+      progress.value = null
+    }
+  }
 
   /**
    * 加载配置
@@ -60,6 +86,9 @@ export function useModManager() {
       loading.value = true
       error.value = null
       mods.value = await invoke<ModInfo[]>('load_all_mods')
+      if (config.value?.settings.autoDetectConflicts) {
+        conflicts.value = await invoke<ModConflict[]>('detect_conflicts', { includeDisabled: false })
+      }
       return mods.value
     }
     catch (e) {
@@ -148,7 +177,7 @@ export function useModManager() {
         multiple: false,
         filters: [{
           name: '压缩包',
-          extensions: ['zip', 'rar', '7z', 'tar.gz'],
+          extensions: ['zip', 'rar', '7z', 'gz', 'tgz', 'xz', 'txz', 'tar'],
         }],
       })
       if (!selected) {
@@ -172,7 +201,7 @@ export function useModManager() {
     try {
       loading.value = true
       error.value = null
-      return await invoke<ArchivePreview>('preview_zip_archive', { archivePath })
+      return await invoke<ArchivePreview>('preview_archive', { archivePath })
     }
     catch (e) {
       error.value = String(e)
@@ -191,15 +220,17 @@ export function useModManager() {
     modName: string,
     nexusId: string | undefined,
     categories: string[],
+    installRoots?: string[],
   ) {
     try {
       loading.value = true
       error.value = null
-      return await invoke<OperationResult>('install_mod', {
+      return await invokeJob<OperationResult>('install_mod', {
         archivePath,
         modName,
         nexusId: nexusId || undefined,
         categories,
+        installRoots,
       })
     }
     catch (e) {
@@ -218,7 +249,7 @@ export function useModManager() {
     try {
       loading.value = true
       error.value = null
-      return await invoke<OperationResult>('enable_mod', { modName })
+      return await invokeJob<OperationResult>('enable_mod', { modName })
     }
     catch (e) {
       error.value = String(e)
@@ -236,7 +267,7 @@ export function useModManager() {
     try {
       loading.value = true
       error.value = null
-      return await invoke<OperationResult>('disable_mod', { modName })
+      return await invokeJob<OperationResult>('disable_mod', { modName })
     }
     catch (e) {
       error.value = String(e)
@@ -265,11 +296,320 @@ export function useModManager() {
     }
   }
 
+  /**
+   * 按当前排序重新部署所有已启用的 MOD
+   */
+  async function redeployAll() {
+    try {
+      loading.value = true
+      error.value = null
+      return await invokeJob<OperationResult>('redeploy_all')
+    }
+    catch (e) {
+      error.value = String(e)
+      throw e
+    }
+    finally {
+      loading.value = false
+    }
+  }
+
+  /**
+   * 检测 MOD 之间的文件冲突
+   */
+  async function detectConflicts(includeDisabled = false) {
+    try {
+      loading.value = true
+      error.value = null
+      conflicts.value = await invoke<ModConflict[]>('detect_conflicts', { includeDisabled })
+      return conflicts.value
+    }
+    catch (e) {
+      error.value = String(e)
+      throw e
+    }
+    finally {
+      loading.value = false
+    }
+  }
+
+  /**
+   * 取消正在运行的后台任务（安装、启用、禁用、移动数据目录等）
+   */
+  async function cancelJob() {
+    if (!currentJobId.value)
+      return
+    try {
+      return await invoke<OperationResult>('cancel_job', { jobId: currentJobId.value })
+    }
+    catch (e) {
+      error.value = String(e)
+      throw e
+    }
+  }
+
+  /**
+   * 将数据目录移动到新位置
+   */
+  async function relocateDataDirectory(newDirectory: string) {
+    try {
+      loading.value = true
+      error.value = null
+      const result = await invokeJob<OperationResult>('relocate_data_directory', { newDirectory })
+      // 重新加载配置，使 dataDirectory 与新位置一致（save_config 会拒绝过期的值）
+      config.value = await invoke<AppConfig>('load_config')
+      return result
+    }
+    catch (e) {
+      error.value = String(e)
+      throw e
+    }
+    finally {
+      loading.value = false
+    }
+  }
+
+  /**
+   * 重命名 MOD
+   */
+  async function renameMod(modName: string, newName: string) {
+    try {
+      loading.value = true
+      error.value = null
+      return await invoke<OperationResult>('rename_mod', { modName, newName })
+    }
+    catch (e) {
+      error.value = String(e)
+      throw e
+    }
+    finally {
+      loading.value = false
+    }
+  }
+
+  /**
+   * 根据压缩包文件名生成可用的 MOD 名称
+   */
+  async function suggestModName(archivePath: string) {
+    try {
+      loading.value = true
+      error.value = null
+      return await invoke<string>('suggest_mod_name', { archivePath })
+    }
+    catch (e) {
+      error.value = String(e)
+      throw e
+    }
+    finally {
+      loading.value = false
+    }
+  }
+
+  /**
+   * 创建方案
+   */
+  async function createProfile(profileName: string) {
+    try {
+      loading.value = true
+      error.value = null
+      return await invoke<OperationResult>('create_profile', { profileName })
+    }
+    catch (e) {
+      error.value = String(e)
+      throw e
+    }
+    finally {
+      loading.value = false
+    }
+  }
+
+  /**
+   * 复制方案
+   */
+  async function cloneProfile(sourceName: string, profileName: string) {
+    try {
+      loading.value = true
+      error.value = null
+      return await invoke<OperationResult>('clone_profile', { sourceName, profileName })
+    }
+    catch (e) {
+      error.value = String(e)
+      throw e
+    }
+    finally {
+      loading.value = false
+    }
+  }
+
+  /**
+   * 删除方案
+   */
+  async function deleteProfile(profileName: string) {
+    try {
+      loading.value = true
+      error.value = null
+      return await invoke<OperationResult>('delete_profile', { profileName })
+    }
+    catch (e) {
+      error.value = String(e)
+      throw e
+    }
+    finally {
+      loading.value = false
+    }
+  }
+
+  /**
+   * 切换方案，只重新部署有变化的文件
+   */
+  async function switchProfile(profileName: string) {
+    try {
+      loading.value = true
+      error.value = null
+      return await invokeJob<OperationResult>('switch_profile', { profileName })
+    }
+    catch (e) {
+      error.value = String(e)
+      throw e
+    }
+    finally {
+      loading.value = false
+    }
+  }
+
+  /**
+   * 调整 MOD 排序，只重新部署胜出 MOD 发生变化的文件
+   */
+  async function reorderMods(modNames: string[]) {
+    try {
+      loading.value = true
+      error.value = null
+      return await invokeJob<OperationResult>('reorder_mods', { modNames })
+    }
+    catch (e) {
+      error.value = String(e)
+      throw e
+    }
+    finally {
+      loading.value = false
+    }
+  }
+
+  /**
+   * 批量启用、禁用或删除 MOD，一次性同步游戏目录
+   */
+  async function batchUpdateMods(changes: ModChange[]) {
+    try {
+      loading.value = true
+      error.value = null
+      return await invokeJob<ModChangeResult[]>('batch_update_mods', { changes })
+    }
+    catch (e) {
+      error.value = String(e)
+      throw e
+    }
+    finally {
+      loading.value = false
+    }
+  }
+
+  /**
+   * 移除所有已部署的文件，将游戏目录恢复到未安装 MOD 的状态（保留启用状态）
+   */
+  async function purgeDeployment() {
+    try {
+      loading.value = true
+      error.value = null
+      return await invokeJob<OperationResult>('purge_deployment')
+    }
+    catch (e) {
+      error.value = String(e)
+      throw e
+    }
+    finally {
+      loading.value = false
+    }
+  }
+
+  /**
+   * 校验游戏目录中的文件是否与部署状态一致
+   */
+  async function verifyDeployment() {
+    try {
+      loading.value = true
+      error.value = null
+      return await invokeJob<VerifyReport>('verify_deployment')
+    }
+    catch (e) {
+      error.value = String(e)
+      throw e
+    }
+    finally {
+      loading.value = false
+    }
+  }
+
+  /**
+   * 只重新部署与部署状态不一致的文件
+   */
+  async function repairDeployment() {
+    try {
+      loading.value = true
+      error.value = null
+      return await invokeJob<OperationResult>('repair_deployment')
+    }
+    catch (e) {
+      error.value = String(e)
+      throw e
+    }
+    finally {
+      loading.value = false
+    }
+  }
+
+  /**
+   * 扫描游戏目录中未被管理的文件
+   */
+  async function scanUntrackedFiles() {
+    try {
+      loading.value = true
+      error.value = null
+      return await invoke<UntrackedFile[]>('scan_untracked_files')
+    }
+    catch (e) {
+      error.value = String(e)
+      throw e
+    }
+    finally {
+      loading.value = false
+    }
+  }
+
+  /**
+   * 将选中的未管理文件收编为新的 MOD
+   */
+  async function adoptUntrackedFiles(modName: string, paths: string[], categories: string[] = []) {
+    try {
+      loading.value = true
+      error.value = null
+      return await invokeJob<OperationResult>('adopt_untracked_files', { modName, paths, categories })
+    }
+    catch (e) {
+      error.value = String(e)
+      throw e
+    }
+    finally {
+      loading.value = false
+    }
+  }
+
   return {
     config,
     mods,
+    conflicts,
     loading,
     error,
+    progress,
     loadConfig,
     saveConfig,
     loadAllMods,
@@ -282,5 +622,22 @@ export function useModManager() {
     enableMod,
     disableMod,
     deleteMod,
+    redeployAll,
+    detectConflicts,
+    cancelJob,
+    relocateDataDirectory,
+    renameMod,
+    suggestModName,
+    createProfile,
+    cloneProfile,
+    deleteProfile,
+    switchProfile,
+    reorderMods,
+    batchUpdateMods,
+    purgeDeployment,
+    verifyDeployment,
+    repairDeployment,
+    scanUntrackedFiles,
+    adoptUntrackedFiles,
   }
 }
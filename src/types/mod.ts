@@ -40,18 +40,36 @@ export interface ModConfigItem {
   enabled: boolean
 }
 
+/**
+ * 部署方式：复制、硬链接或符号链接（无法链接时退化为复制）
+ */
+export type DeployMethod = 'copy' | 'hardlink' | 'symlink'
+
+/**
+ * 方案：一套 MOD 排序和启用状态
+ */
+export interface Profile {
+  name: string
+  mods: ModConfigItem[]
+}
+
 /**
  * 全局配置（config.json）
  */
 export interface AppConfig {
   version: string
+  schemaVersion: number
   gameDirectory: string
-  dataDirectory: string
+  /** 只读：数据目录位置，只能通过 relocateDataDirectory 修改 */
+  readonly dataDirectory: string
   mods: ModConfigItem[]
+  activeProfile: string
+  profiles: Profile[]
   categories: Category[]
   settings: {
     autoDetectConflicts: boolean
     showConflictWarnings: boolean
+    deployMethod: DeployMethod
   }
 }
 
@@ -70,6 +88,15 @@ export interface Mod {
   conflictWith?: string[]
 }
 
+/**
+ * 两个 MOD 之间的文件冲突
+ */
+export interface ModConflict {
+  winner: string
+  loser: string
+  files: string[]
+}
+
 /**
  * MOD 安装表单数据
  */
@@ -85,6 +112,10 @@ export interface ModInstallFormData {
 export interface ArchivePreview {
   hasNativePC: boolean
   nativePCPath: string
+  installRoot: string
+  installRoots: string[]
+  fileCount: number
+  totalSize: number
   files: ArchiveFileNode[]
 }
 
@@ -95,6 +126,10 @@ export interface ArchiveFileNode {
   name: string
   path: string
   isDirectory: boolean
+  size: number
+  fileCount: number
+  isNativePcRoot: boolean
+  landsInGameRoot: boolean
   children?: ArchiveFileNode[]
 }
 
@@ -102,8 +137,11 @@ export interface ArchiveFileNode {
  * 文件操作进度
  */
 export interface FileProgress {
+  jobId: string
   current: number
   total: number
+  bytes: number
+  totalBytes: number
   percentage: number
   currentFile?: string
 }
@@ -115,4 +153,53 @@ export interface OperationResult {
   success: boolean
   message?: string
   error?: string
+  skipped: string[]
+}
+
+/**
+ * 批量操作中对单个 MOD 的修改
+ */
+export type ModAction = 'enable' | 'disable' | 'delete'
+
+export interface ModChange {
+  name: string
+  action: ModAction
+}
+
+/**
+ * 批量操作中单个 MOD 的结果
+ */
+export interface ModChangeResult extends OperationResult {
+  name: string
+  action: ModAction
+}
+
+/**
+ * 部署偏差的类型：缺失、被修改、不应存在
+ */
+export type DriftKind = 'missing' | 'modified' | 'unexpected'
+
+/**
+ * 与部署状态不一致的文件
+ */
+export interface DriftedFile {
+  path: string
+  owner: string
+  kind: DriftKind
+}
+
+/**
+ * 部署校验结果
+ */
+export interface VerifyReport {
+  checked: number
+  drifted: DriftedFile[]
+}
+
+/**
+ * 游戏目录中未被管理的文件
+ */
+export interface UntrackedFile {
+  path: string
+  size: number
 }